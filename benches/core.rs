@@ -0,0 +1,164 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use libprocon_rs::disjoint_set::DisjointSet;
+use libprocon_rs::fenwick::FenwickTree;
+use libprocon_rs::graph::{DirectedGraph, WeightedGraph};
+use libprocon_rs::modint::{dot_product, Mod};
+use libprocon_rs::radix_sort::radix_sort;
+use libprocon_rs::rng::XorShift64;
+use libprocon_rs::segment_tree::SegmentTree;
+
+const N: usize = 200_000;
+const Q: usize = 200_000;
+
+fn bench(name: &str, f: impl FnOnce() -> u64) {
+    let start = Instant::now();
+    black_box(f());
+    println!("{:<24} {:>10.2?}", name, start.elapsed());
+}
+
+fn random_range(rng: &mut XorShift64, n: usize) -> (usize, usize) {
+    let begin = rng.gen_range(0..n);
+    let end = rng.gen_range(begin + 1..n + 1);
+    (begin, end)
+}
+
+fn bench_segment_tree() {
+    let mut rng = XorShift64::new(1);
+    bench("segment_tree", || {
+        let mut segtree = SegmentTree::<usize>::new(N);
+        let mut acc = 0;
+        for _ in 0..Q {
+            let (begin, end) = random_range(&mut rng, N);
+            segtree.update(begin..end, rng.gen_range(0..1000));
+            let (begin, end) = random_range(&mut rng, N);
+            acc ^= segtree.query(begin..end) as u64;
+        }
+        acc
+    });
+}
+
+fn bench_fenwick() {
+    let mut rng = XorShift64::new(2);
+    bench("fenwick", || {
+        let mut ft = FenwickTree::<isize>::new(N);
+        let mut acc = 0;
+        for _ in 0..Q {
+            let (begin, end) = random_range(&mut rng, N);
+            ft.add(begin..end, rng.gen_range(0..1000) as isize);
+            let (begin, end) = random_range(&mut rng, N);
+            acc ^= ft.sum(begin..end) as u64;
+        }
+        acc
+    });
+}
+
+fn bench_disjoint_set() {
+    let mut rng = XorShift64::new(3);
+    bench("disjoint_set", || {
+        let mut dsu = DisjointSet::new(N);
+        let mut acc = 0;
+        for _ in 0..Q {
+            let u = rng.gen_range(0..N);
+            let v = rng.gen_range(0..N);
+            acc ^= dsu.union(u, v) as u64;
+        }
+        acc
+    });
+}
+
+fn bench_sccs() {
+    let mut rng = XorShift64::new(4);
+    let mut graph = DirectedGraph::new(N);
+    for _ in 0..2 * N {
+        graph.add_edge(rng.gen_range(0..N), rng.gen_range(0..N));
+    }
+    bench("find_sccs", || graph.find_sccs().len() as u64);
+}
+
+fn bench_dijkstra() {
+    let mut rng = XorShift64::new(7);
+    let mut graph = WeightedGraph::new(N);
+    for _ in 0..5 * N {
+        graph.add_edge(rng.gen_range(0..N), rng.gen_range(0..N), rng.gen_range(0..1_000_000) as u64);
+    }
+    bench("dijkstra", || graph.dijkstra(0).iter().flatten().fold(0, |acc, &d| acc ^ d));
+}
+
+fn bench_radix_sort() {
+    let mut rng = XorShift64::new(5);
+    let values: Vec<u64> = (0..10 * N).map(|_| rng.next_u64()).collect();
+    let mut a = values.clone();
+    bench("sort_unstable", || {
+        a.sort_unstable();
+        a[0]
+    });
+    let mut a = values;
+    bench("radix_sort", || {
+        radix_sort(&mut a);
+        a[0]
+    });
+}
+
+fn bench_modint() {
+    let mut rng = XorShift64::new(6);
+    let values: Vec<Mod<998_244_353>> = (0..N).map(|_| Mod::from(rng.next_u64())).collect();
+    bench("modint_mul", || {
+        let mut acc = Mod::new(1);
+        for _ in 0..50 {
+            for &x in &values {
+                acc = acc * x + x;
+            }
+        }
+        acc.value as u64
+    });
+    let large: Vec<Mod<2_305_843_009_213_693_951>> = (0..N).map(|_| Mod::from(rng.next_u64())).collect();
+    bench("modint_mul_large", || {
+        let mut acc = Mod::new(1);
+        for _ in 0..50 {
+            for &x in &large {
+                acc = acc * x + x;
+            }
+        }
+        acc.value as u64
+    });
+    bench("modint_mul_large_indep", || {
+        let mut acc = Mod::new(0);
+        for _ in 0..50 {
+            acc += large.windows(2).map(|w| w[0] * w[1]).sum();
+        }
+        acc.value as u64
+    });
+    let other: Vec<Mod<998_244_353>> = values.iter().rev().copied().collect();
+    bench("modint_dot_naive", || {
+        let mut acc = Mod::new(0);
+        for _ in 0..50 {
+            acc += values.iter().zip(&other).map(|(&x, &y)| x * y).sum();
+        }
+        acc.value as u64
+    });
+    bench("modint_dot_product", || {
+        let mut acc = Mod::new(0);
+        for _ in 0..50 {
+            acc += dot_product(&values, &other);
+        }
+        acc.value as u64
+    });
+}
+
+fn main() {
+    bench_segment_tree();
+    bench_fenwick();
+    bench_disjoint_set();
+    bench_dijkstra();
+    bench_radix_sort();
+    bench_modint();
+    // The recursive DFS needs more than the default main-thread stack.
+    std::thread::Builder::new()
+        .stack_size(1 << 28)
+        .spawn(bench_sccs)
+        .unwrap()
+        .join()
+        .unwrap();
+}
\ No newline at end of file
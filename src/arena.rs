@@ -0,0 +1,84 @@
+use std::ops::{Index, IndexMut};
+
+/// Append-only node storage for recursive structures. Nodes are referred to by
+/// their `usize` index, which stays valid for the arena's lifetime.
+#[derive(Clone)]
+pub struct Arena<T> {
+    nodes: Vec<T>,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { nodes: Vec::with_capacity(capacity) }
+    }
+
+    /// Stores `node` and returns its index.
+    pub fn alloc(&mut self, node: T) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> Index<usize> for Arena<T> {
+    type Output = T;
+    fn index(&self, idx: usize) -> &T { &self.nodes[idx] }
+}
+
+impl<T> IndexMut<usize> for Arena<T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T { &mut self.nodes[idx] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arena;
+
+    // Persistent stack: every push creates a new version sharing its tail.
+    struct Node {
+        value: i32,
+        next: Option<usize>,
+    }
+
+    fn to_vec(arena: &Arena<Node>, mut top: Option<usize>) -> Vec<i32> {
+        let mut result = Vec::new();
+        while let Some(idx) = top {
+            result.push(arena[idx].value);
+            top = arena[idx].next;
+        }
+        result
+    }
+
+    #[test]
+    fn test_persistent_stack() {
+        let mut arena = Arena::new();
+        let a = arena.alloc(Node { value: 1, next: None });
+        let b = arena.alloc(Node { value: 2, next: Some(a) });
+        let c = arena.alloc(Node { value: 3, next: Some(a) });
+        assert_eq!(to_vec(&arena, Some(b)), [2, 1]);
+        assert_eq!(to_vec(&arena, Some(c)), [3, 1]);
+        assert_eq!(to_vec(&arena, arena[c].next), [1]);
+
+        arena[a].value = 10;
+        assert_eq!(to_vec(&arena, Some(b)), [2, 10]);
+        assert_eq!(arena.len(), 3);
+    }
+}
\ No newline at end of file
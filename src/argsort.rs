@@ -0,0 +1,46 @@
+/// Indices that stably sort `a`.
+pub fn argsort<T>(a: &[T]) -> Vec<usize> where
+    T: Ord {
+    let mut order: Vec<usize> = (0..a.len()).collect();
+    order.sort_by_key(|&i| &a[i]);
+    order
+}
+
+/// 0-based dense ranks: equal values share a rank and ranks have no gaps
+/// (i.e. coordinate compression).
+pub fn ranks<T>(a: &[T]) -> Vec<usize> where
+    T: Ord {
+    let order = argsort(a);
+    let mut result = vec![0; a.len()];
+    for k in 1..order.len() {
+        let (prev, i) = (order[k - 1], order[k]);
+        result[i] = result[prev] + usize::from(a[prev] != a[i]);
+    }
+    result
+}
+
+/// 0-based competition ranks ("1224" ranking): the number of strictly smaller elements.
+pub fn competition_ranks<T>(a: &[T]) -> Vec<usize> where
+    T: Ord {
+    let order = argsort(a);
+    let mut result = vec![0; a.len()];
+    for k in 1..order.len() {
+        let (prev, i) = (order[k - 1], order[k]);
+        result[i] = if a[prev] == a[i] { result[prev] } else { k };
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranks() {
+        let a = [30, 10, 20, 10, 40];
+        assert_eq!(argsort(&a), [1, 3, 2, 0, 4]);
+        assert_eq!(ranks(&a), [2, 0, 1, 0, 3]);
+        assert_eq!(competition_ranks(&a), [3, 0, 2, 0, 4]);
+        assert!(ranks::<i32>(&[]).is_empty());
+    }
+}
\ No newline at end of file
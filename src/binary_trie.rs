@@ -0,0 +1,240 @@
+use crate::arena::Arena;
+
+#[derive(Clone, Copy, Default)]
+struct Node {
+    children: [usize; 2],
+    count: usize,
+}
+
+/// Multiset of `BITS`-bit integers stored bit by bit from the top.
+#[derive(Clone)]
+pub struct BinaryTrie<const BITS: usize> {
+    nodes: Arena<Node>, // child 0 means none, since the root is never a child
+}
+
+impl<const BITS: usize> BinaryTrie<BITS> {
+    pub fn new() -> Self {
+        let mut nodes = Arena::new();
+        nodes.alloc(Node::default());
+        Self { nodes }
+    }
+
+    pub fn insert(&mut self, x: u64) {
+        let mut node = 0;
+        self.nodes[node].count += 1;
+        for b in (0..BITS).rev() {
+            let bit = (x >> b & 1) as usize;
+            if self.nodes[node].children[bit] == 0 {
+                self.nodes[node].children[bit] = self.nodes.alloc(Node::default());
+            }
+            node = self.nodes[node].children[bit];
+            self.nodes[node].count += 1;
+        }
+    }
+
+    /// Removes one copy of `x`, returning whether there was one.
+    pub fn erase(&mut self, x: u64) -> bool {
+        if self.count(x) == 0 { return false; }
+        let mut node = 0;
+        self.nodes[node].count -= 1;
+        for b in (0..BITS).rev() {
+            node = self.nodes[node].children[(x >> b & 1) as usize];
+            self.nodes[node].count -= 1;
+        }
+        true
+    }
+
+    pub fn count(&self, x: u64) -> usize {
+        let mut node = 0;
+        for b in (0..BITS).rev() {
+            node = self.nodes[node].children[(x >> b & 1) as usize];
+            if node == 0 { return 0; }
+        }
+        self.nodes[node].count
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// min over stored y of x ^ y
+    pub fn min_xor_with(&self, x: u64) -> Option<u64> {
+        self.xor_extremum(x, false)
+    }
+
+    /// max over stored y of x ^ y
+    pub fn max_xor_with(&self, x: u64) -> Option<u64> {
+        self.xor_extremum(x, true)
+    }
+
+    /// 0-indexed, counting duplicates.
+    pub fn kth_smallest(&self, mut k: usize) -> Option<u64> {
+        if k >= self.len() { return None; }
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..BITS).rev() {
+            let zero = self.nodes[node].children[0];
+            let zeros = if zero == 0 { 0 } else { self.nodes[zero].count };
+            if k < zeros {
+                node = zero;
+            } else {
+                k -= zeros;
+                node = self.nodes[node].children[1];
+                result |= 1 << b;
+            }
+        }
+        Some(result)
+    }
+
+    fn xor_extremum(&self, x: u64, maximize: bool) -> Option<u64> {
+        if self.is_empty() { return None; }
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..BITS).rev() {
+            let preferred = (x >> b & 1) as usize ^ maximize as usize;
+            let child = self.nodes[node].children[preferred];
+            if child != 0 && self.nodes[child].count > 0 {
+                node = child;
+                if maximize {
+                    result |= 1 << b;
+                }
+            } else {
+                node = self.nodes[node].children[preferred ^ 1];
+                if !maximize {
+                    result |= 1 << b;
+                }
+            }
+        }
+        Some(result)
+    }
+}
+
+impl<const BITS: usize> Default for BinaryTrie<BITS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fully persistent `BinaryTrie`; a version is identified by its root node.
+/// Version `0` is the empty trie.
+#[derive(Clone)]
+pub struct PersistentBinaryTrie<const BITS: usize> {
+    nodes: Arena<Node>, // node 0 is the empty node and its own child
+}
+
+impl<const BITS: usize> PersistentBinaryTrie<BITS> {
+    pub const EMPTY: usize = 0;
+
+    pub fn new() -> Self {
+        let mut nodes = Arena::new();
+        nodes.alloc(Node::default());
+        Self { nodes }
+    }
+
+    /// Returns the version obtained by adding `x` to `version`.
+    pub fn insert(&mut self, version: usize, x: u64) -> usize {
+        let root = self.copy_with_increment(version);
+        let (mut new, mut old) = (root, version);
+        for b in (0..BITS).rev() {
+            let bit = (x >> b & 1) as usize;
+            let old_child = self.nodes[old].children[bit];
+            let new_child = self.copy_with_increment(old_child);
+            self.nodes[new].children[bit] = new_child;
+            new = new_child;
+            old = old_child;
+        }
+        root
+    }
+
+    pub fn len(&self, version: usize) -> usize {
+        self.nodes[version].count
+    }
+
+    /// max of x ^ y over the values in `newer` but not in `older`,
+    /// where `newer` was derived from `older` by insertions.
+    pub fn max_xor_between(&self, older: usize, newer: usize, x: u64) -> Option<u64> {
+        if self.nodes[newer].count == self.nodes[older].count { return None; }
+        let (mut old, mut new) = (older, newer);
+        let mut result = 0;
+        for b in (0..BITS).rev() {
+            let preferred = (x >> b & 1) as usize ^ 1;
+            let count = self.nodes[self.nodes[new].children[preferred]].count
+                - self.nodes[self.nodes[old].children[preferred]].count;
+            let bit = if count > 0 {
+                result |= 1 << b;
+                preferred
+            } else {
+                preferred ^ 1
+            };
+            old = self.nodes[old].children[bit];
+            new = self.nodes[new].children[bit];
+        }
+        Some(result)
+    }
+
+    fn copy_with_increment(&mut self, node: usize) -> usize {
+        let copy = Node { count: self.nodes[node].count + 1, ..self.nodes[node] };
+        self.nodes.alloc(copy)
+    }
+}
+
+impl<const BITS: usize> Default for PersistentBinaryTrie<BITS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xor_queries() {
+        let mut trie = BinaryTrie::<32>::new();
+        assert_eq!(trie.min_xor_with(5), None);
+        for x in [3, 10, 12] {
+            trie.insert(x);
+        }
+        assert_eq!(trie.min_xor_with(8), Some(8 ^ 10));
+        assert_eq!(trie.max_xor_with(8), Some(8 ^ 3));
+        assert!(trie.erase(10));
+        assert!(!trie.erase(10));
+        assert_eq!(trie.min_xor_with(8), Some(8 ^ 12));
+    }
+
+    #[test]
+    fn test_kth_smallest() {
+        let mut trie = BinaryTrie::<64>::new();
+        for x in [7, 1, u64::MAX, 7, 4] {
+            trie.insert(x);
+        }
+        let sorted: Vec<_> = (0..trie.len()).map(|k| trie.kth_smallest(k).unwrap()).collect();
+        assert_eq!(sorted, vec![1, 4, 7, 7, u64::MAX]);
+        assert_eq!(trie.kth_smallest(5), None);
+        assert_eq!(trie.count(7), 2);
+    }
+
+    #[test]
+    fn test_max_xor_in_subarray() {
+        let a = [5, 1, 9, 12, 3, 6];
+        let mut trie = PersistentBinaryTrie::<4>::new();
+        let mut versions = vec![PersistentBinaryTrie::<4>::EMPTY];
+        for &x in &a {
+            let next = trie.insert(*versions.last().unwrap(), x);
+            versions.push(next);
+        }
+        for l in 0..a.len() {
+            for r in l..=a.len() {
+                for x in 0..16 {
+                    let expected = a[l..r].iter().map(|&y| x ^ y).max();
+                    assert_eq!(trie.max_xor_between(versions[l], versions[r], x), expected);
+                }
+            }
+        }
+        assert_eq!(trie.len(versions[3]), 3);
+    }
+}
\ No newline at end of file
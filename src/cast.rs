@@ -0,0 +1,62 @@
+/// Integer conversions that are plain `as` casts in release builds but assert
+/// in debug builds that the value fits.
+pub trait CheckedCast: Copy {
+    fn as_usize(self) -> usize;
+    fn as_i64(self) -> i64;
+    fn as_u64(self) -> u64;
+}
+
+macro_rules! impl_checked_cast {
+    ($($t:ty),*) => {$(
+        impl CheckedCast for $t {
+            fn as_usize(self) -> usize {
+                debug_assert!(usize::try_from(self).is_ok(), "{} does not fit in usize", self);
+                self as usize
+            }
+
+            fn as_i64(self) -> i64 {
+                debug_assert!(i64::try_from(self).is_ok(), "{} does not fit in i64", self);
+                self as i64
+            }
+
+            fn as_u64(self) -> u64 {
+                debug_assert!(u64::try_from(self).is_ok(), "{} does not fit in u64", self);
+                self as u64
+            }
+        }
+    )*};
+}
+
+impl_checked_cast!(i32, i64, isize, u32, u64, usize);
+
+pub fn as_usize(x: impl CheckedCast) -> usize {
+    x.as_usize()
+}
+
+pub fn as_i64(x: impl CheckedCast) -> i64 {
+    x.as_i64()
+}
+
+pub fn as_u64(x: impl CheckedCast) -> u64 {
+    x.as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_casts() {
+        assert_eq!(as_usize(5_i64), 5);
+        assert_eq!(as_i64(7_usize), 7);
+        assert_eq!((-3_i32).as_i64(), -3);
+        assert_eq!(u32::MAX.as_u64(), u32::MAX as u64);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "-1 does not fit in usize")]
+    fn test_negative_index_panics_in_debug() {
+        as_usize(-1_i64);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use crate::modint::Mod;
+
+/// Factorials and inverse factorials modulo a prime `N`, up to a fixed bound.
+pub struct Combinatorics<const N: usize> {
+    fact: Vec<Mod<N>>,
+    inv_fact: Vec<Mod<N>>,
+}
+
+impl<const N: usize> Combinatorics<N> {
+    /// `max` must be less than `N`.
+    pub fn new(max: usize) -> Self {
+        assert!(max < N, "factorials up to {} vanish modulo {}", max, N);
+        let mut fact = vec![Mod::new(1); max + 1];
+        for i in 1..=max {
+            fact[i] = fact[i - 1] * Mod::new(i);
+        }
+        let mut inv_fact = vec![fact[max].inv(); max + 1];
+        for i in (1..=max).rev() {
+            inv_fact[i - 1] = inv_fact[i] * Mod::new(i);
+        }
+        Self { fact, inv_fact }
+    }
+
+    pub fn fact(&self, n: usize) -> Mod<N> {
+        self.fact[n]
+    }
+
+    pub fn inv_fact(&self, n: usize) -> Mod<N> {
+        self.inv_fact[n]
+    }
+
+    pub fn comb(&self, n: usize, k: usize) -> Mod<N> {
+        if k > n { return Mod::new(0); }
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    pub fn perm(&self, n: usize, k: usize) -> Mod<N> {
+        if k > n { return Mod::new(0); }
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// Ways to choose `k` of `n` kinds with repetition; needs the table up to `n + k - 1`.
+    pub fn multiset(&self, n: usize, k: usize) -> Mod<N> {
+        if n == 0 { return Mod::new((k == 0) as usize); }
+        self.comb(n + k - 1, k)
+    }
+
+    /// Needs the table up to `2 * n`.
+    pub fn catalan(&self, n: usize) -> Mod<N> {
+        self.comb(2 * n, n) - self.comb(2 * n, n + 1)
+    }
+}
+
+/// Pascal's triangle as a constant: `table[n][k]` is `C(n, k)` for `n < N`, `k < K`.
+/// Entries must fit in `u64` (all do for `N <= 68`); evaluating it in a `const` turns
+/// overflow into a compile error, as does a `static`.
+pub const fn binomial_table<const N: usize, const K: usize>() -> [[u64; K]; N] {
+    let mut table = [[0; K]; N];
+    let mut n = 0;
+    while n < N {
+        if K > 0 {
+            table[n][0] = 1;
+        }
+        let mut k = 1;
+        while k < K && k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let c = Combinatorics::<998_244_353>::new(20);
+        assert_eq!(c.comb(5, 2).value, 10);
+        assert_eq!(c.comb(2, 5).value, 0);
+        assert_eq!(c.perm(5, 2).value, 20);
+        assert_eq!(c.multiset(3, 2).value, 6);
+        assert_eq!(c.multiset(0, 0).value, 1);
+        assert_eq!(c.multiset(0, 3).value, 0);
+        let catalans: Vec<_> = (0..8).map(|n| c.catalan(n).value).collect();
+        assert_eq!(catalans, [1, 1, 2, 5, 14, 42, 132, 429]);
+        assert_eq!((c.fact(20) * c.inv_fact(20)).value, 1);
+    }
+
+    #[test]
+    fn test_binomial_table() {
+        const BINOM: [[u64; 8]; 68] = binomial_table();
+        assert_eq!(BINOM[5][2], 10);
+        assert_eq!(BINOM[2][5], 0);
+        assert_eq!(BINOM[0][0], 1);
+        assert_eq!(BINOM[67][7], 869_648_208);
+        let c = Combinatorics::<998_244_353>::new(67);
+        for (n, row) in BINOM.iter().enumerate() {
+            for (k, &value) in row.iter().enumerate() {
+                assert_eq!(value as usize % 998_244_353, c.comb(n, k).value);
+            }
+        }
+        static FULL: [[u64; 68]; 68] = binomial_table();
+        assert_eq!(FULL[67][33], 14_226_520_737_620_288_370);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::fenwick::{FenwickCompatible, PointFenwickTree};
+
+/// `PointFenwickTree` over arbitrary ordered keys, all of which are given up front.
+#[derive(Clone)]
+pub struct CompressedFenwick<K, T> where
+    T: FenwickCompatible {
+    keys: Vec<K>,
+    tree: PointFenwickTree<T>,
+}
+
+impl<K, T> CompressedFenwick<K, T> where
+    K: Ord,
+    T: FenwickCompatible {
+    pub fn new(keys: impl IntoIterator<Item = K>) -> Self {
+        let mut keys: Vec<K> = keys.into_iter().collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let tree = PointFenwickTree::new(keys.len());
+        Self { keys, tree }
+    }
+
+    /// Panics if `key` was not given to `new`.
+    pub fn add(&mut self, key: K, val: T) {
+        let idx = self.keys.binary_search(&key).expect("key was not registered");
+        self.tree.add(idx, val);
+    }
+
+    /// Sum over the registered keys in `range`; its bounds need not be registered.
+    pub fn sum(&self, range: impl RangeBounds<K>) -> T {
+        let begin = match range.start_bound() {
+            Bound::Included(key) => self.keys.partition_point(|k| k < key),
+            Bound::Excluded(key) => self.keys.partition_point(|k| k <= key),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.keys.partition_point(|k| k <= key),
+            Bound::Excluded(key) => self.keys.partition_point(|k| k < key),
+            Bound::Unbounded => self.keys.len(),
+        };
+        if begin >= end { return T::zero(); }
+        self.tree.sum(begin..end)
+    }
+
+    /// Number of distinct registered keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompressedFenwick;
+
+    #[test]
+    fn test_sparse_keys() {
+        let events = [(1_000_000_000_i64, 3), (-5, 1), (42, 2), (-5, 4)];
+        let mut ft = CompressedFenwick::<i64, isize>::new(events.iter().map(|&(t, _)| t));
+        assert_eq!(ft.len(), 3);
+        for (t, v) in events {
+            ft.add(t, v);
+        }
+        assert_eq!(ft.sum(..), 10);
+        assert_eq!(ft.sum(..42), 5);
+        assert_eq!(ft.sum(..=42), 7);
+        assert_eq!(ft.sum(0..2_000_000_000), 5);
+        assert_eq!(ft.sum(43..1_000_000_000), 0);
+        assert_eq!(ft.sum((std::ops::Bound::Excluded(-5), std::ops::Bound::Unbounded)), 5);
+    }
+}
\ No newline at end of file
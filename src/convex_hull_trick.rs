@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::ops::Bound;
+
+/// Upper envelope of lines `y = k * x + m` answering max queries at integer `x`.
+/// Coefficients and query results must fit in `i64`.
+#[derive(Clone, Default)]
+pub struct LineContainer {
+    lines: BTreeMap<i64, (i64, i64)>, // slope -> (intercept, last x at which the line is optimal)
+    ends: BTreeSet<(i64, i64)>,       // (last x at which the line is optimal, slope)
+}
+
+impl LineContainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn insert(&mut self, k: i64, m: i64) {
+        if let Some(&(old_m, _)) = self.lines.get(&k) {
+            if old_m >= m { return; }
+            self.erase(k);
+        }
+        self.lines.insert(k, (m, i64::MAX));
+        self.ends.insert((i64::MAX, k));
+        while self.intersect(k, self.next(k)) {
+            let next = self.next(k).unwrap();
+            self.erase(next);
+        }
+        let Some(prev) = self.prev(k) else { return; };
+        if self.intersect(prev, Some(k)) {
+            self.erase(k);
+            self.intersect(prev, self.next(prev));
+        }
+        let mut current = prev;
+        while let Some(prev) = self.prev(current) {
+            if self.end(prev) < self.end(current) { break; }
+            self.erase(current);
+            self.intersect(prev, self.next(prev));
+            current = prev;
+        }
+    }
+
+    /// max over inserted lines of k * x + m
+    pub fn query(&self, x: i64) -> Option<i64> {
+        let &(_, k) = self.ends.range((x, i64::MIN)..).next()?;
+        let (m, _) = self.lines[&k];
+        Some(k * x + m)
+    }
+
+    /// Sets where `x` stops being optimal against its successor `y` and reports
+    /// whether `y` is then never optimal.
+    fn intersect(&mut self, x: i64, y: Option<i64>) -> bool {
+        let Some(y) = y else {
+            self.set_end(x, i64::MAX);
+            return false;
+        };
+        let (mx, _) = self.lines[&x];
+        let (my, end_y) = self.lines[&y];
+        // Differences of i64 coefficients can overflow, so divide in i128.
+        let end_x = floor_div(my as i128 - mx as i128, x as i128 - y as i128);
+        let end_x = end_x.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        self.set_end(x, end_x);
+        end_x >= end_y
+    }
+
+    fn end(&self, k: i64) -> i64 {
+        self.lines[&k].1
+    }
+
+    fn set_end(&mut self, k: i64, end: i64) {
+        let line = self.lines.get_mut(&k).unwrap();
+        self.ends.remove(&(line.1, k));
+        line.1 = end;
+        self.ends.insert((end, k));
+    }
+
+    fn erase(&mut self, k: i64) {
+        let (_, end) = self.lines.remove(&k).unwrap();
+        self.ends.remove(&(end, k));
+    }
+
+    fn next(&self, k: i64) -> Option<i64> {
+        self.lines.range((Bound::Excluded(k), Bound::Unbounded)).next().map(|(&k, _)| k)
+    }
+
+    fn prev(&self, k: i64) -> Option<i64> {
+        self.lines.range(..k).next_back().map(|(&k, _)| k)
+    }
+}
+
+/// Upper envelope for lines added in non-decreasing slope order, answering max queries.
+#[derive(Clone, Default)]
+pub struct MonotoneCht {
+    lines: VecDeque<(i64, i64)>,
+}
+
+impl MonotoneCht {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_line(&mut self, k: i64, m: i64) {
+        if let Some(&(last_k, last_m)) = self.lines.back() {
+            assert!(k >= last_k, "slopes must be added in non-decreasing order");
+            if k == last_k {
+                if m <= last_m { return; }
+                self.lines.pop_back();
+            }
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if !is_redundant(self.lines[n - 2], self.lines[n - 1], (k, m)) { break; }
+            self.lines.pop_back();
+        }
+        self.lines.push_back((k, m));
+    }
+
+    /// For `x` non-decreasing across calls: drops lines that can no longer win, amortized O(1).
+    pub fn query_monotone(&mut self, x: i64) -> Option<i64> {
+        while self.lines.len() >= 2 && eval(self.lines[0], x) <= eval(self.lines[1], x) {
+            self.lines.pop_front();
+        }
+        self.lines.front().map(|&line| eval(line, x))
+    }
+
+    /// Any `x`, O(log n).
+    pub fn query(&self, x: i64) -> Option<i64> {
+        if self.lines.is_empty() { return None; }
+        // Values along the envelope at a fixed x increase and then decrease.
+        let (mut lo, mut hi) = (0, self.lines.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if eval(self.lines[mid], x) < eval(self.lines[mid + 1], x) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(eval(self.lines[lo], x))
+    }
+}
+
+fn eval((k, m): (i64, i64), x: i64) -> i64 {
+    k * x + m
+}
+
+/// Whether `mid` never beats both neighbors, for slopes `left.0 < mid.0 < right.0`.
+fn is_redundant(left: (i64, i64), mid: (i64, i64), right: (i64, i64)) -> bool {
+    let (k1, m1) = (left.0 as i128, left.1 as i128);
+    let (k2, m2) = (mid.0 as i128, mid.1 as i128);
+    let (k3, m3) = (right.0 as i128, right.1 as i128);
+    (m1 - m3) * (k2 - k1) <= (m1 - m2) * (k3 - k1)
+}
+
+fn floor_div(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) { q - 1 } else { q }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_line_container_against_naive() {
+        let mut rng = XorShift64::new(11);
+        for _ in 0..50 {
+            let mut container = LineContainer::new();
+            let mut lines = Vec::new();
+            assert_eq!(container.query(0), None);
+            for _ in 0..30 {
+                let k = rng.gen_range(0..41) as i64 - 20;
+                let m = rng.gen_range(0..201) as i64 - 100;
+                container.insert(k, m);
+                lines.push((k, m));
+                for x in -30..=30 {
+                    let expected = lines.iter().map(|&(k, m)| k * x + m).max();
+                    assert_eq!(container.query(x), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_container_extreme_coefficients() {
+        let mut container = LineContainer::new();
+        // The lines cross at x = 1e19, past i64::MAX.
+        container.insert(1, -5_000_000_000_000_000_000);
+        container.insert(0, 5_000_000_000_000_000_000);
+        assert_eq!(container.query(0), Some(5_000_000_000_000_000_000));
+        assert_eq!(container.query(i64::MAX), Some(5_000_000_000_000_000_000));
+        container.insert(-2, -4_000_000_000_000_000_000);
+        container.insert(2, -4_000_000_000_000_000_000);
+        for x in [i64::MIN / 2 + 1, -4_500_000_000_000_000_000, 0, 4_500_000_000_000_000_000, i64::MAX / 2] {
+            let lines = [(1, -5e18 as i64), (0, 5e18 as i64), (-2, -4e18 as i64), (2, -4e18 as i64)];
+            let expected = lines.iter().filter_map(|&(k, m)| (k as i128 * x as i128 + m as i128).try_into().ok()).max();
+            assert_eq!(container.query(x), expected);
+        }
+    }
+
+    #[test]
+    fn test_monotone_cht_against_naive() {
+        let mut rng = XorShift64::new(12);
+        for _ in 0..50 {
+            let mut slopes: Vec<i64> = (0..20).map(|_| rng.gen_range(0..41) as i64 - 20).collect();
+            slopes.sort_unstable();
+            let mut cht = MonotoneCht::new();
+            let mut monotone = MonotoneCht::new();
+            let mut lines = Vec::new();
+            for &k in &slopes {
+                let m = rng.gen_range(0..201) as i64 - 100;
+                cht.add_line(k, m);
+                monotone.add_line(k, m);
+                lines.push((k, m));
+                for x in -30..=30 {
+                    let expected = lines.iter().map(|&(k, m)| k * x + m).max();
+                    assert_eq!(cht.query(x), expected);
+                }
+            }
+            for x in -30..=30 {
+                let expected = lines.iter().map(|&(k, m)| k * x + m).max();
+                assert_eq!(monotone.query_monotone(x), expected);
+            }
+        }
+    }
+}
\ No newline at end of file
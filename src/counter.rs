@@ -0,0 +1,179 @@
+use std::hash::Hash;
+use std::ops::{Add, BitAnd, BitOr, Sub};
+
+use crate::fast_hash::FastHashMap;
+
+/// Multiset of counts in the spirit of Python's `collections.Counter`.
+/// Only positive counts are stored.
+#[derive(Clone)]
+pub struct Counter<T> {
+    counts: FastHashMap<T, usize>,
+}
+
+impl<T> Counter<T> where
+    T: Hash + Eq {
+    pub fn new() -> Self {
+        Self { counts: FastHashMap::default() }
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_insert(0) += n;
+        }
+    }
+
+    /// Removes one occurrence; the count never goes below zero.
+    pub fn sub(&mut self, item: &T) {
+        self.sub_n(item, 1);
+    }
+
+    pub fn sub_n(&mut self, item: &T, n: usize) {
+        if let Some(count) = self.counts.get_mut(item) {
+            if *count > n {
+                *count -= n;
+            } else {
+                self.counts.remove(item);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+}
+
+impl<T> Counter<T> where
+    T: Hash + Ord + Clone {
+    /// The `k` most frequent items, ties broken by the smaller item.
+    pub fn most_common(&self, k: usize) -> Vec<(T, usize)> {
+        let mut result: Vec<_> = self.iter().map(|(item, count)| (item.clone(), count)).collect();
+        result.sort_unstable_by(|(a, x), (b, y)| y.cmp(x).then_with(|| a.cmp(b)));
+        result.truncate(k);
+        result
+    }
+}
+
+impl<T> Default for Counter<T> where
+    T: Hash + Eq {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> FromIterator<T> for Counter<T> where
+    T: Hash + Eq {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<T> Extend<T> for Counter<T> where
+    T: Hash + Eq {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T> Add for Counter<T> where
+    T: Hash + Eq {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        for (item, count) in rhs.counts {
+            self.add_n(item, count);
+        }
+        self
+    }
+}
+
+/// Keeps only positive differences.
+impl<T> Sub for Counter<T> where
+    T: Hash + Eq {
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self {
+        for (item, count) in rhs.counts {
+            self.sub_n(&item, count);
+        }
+        self
+    }
+}
+
+/// Maximum of counts (multiset union).
+impl<T> BitOr for Counter<T> where
+    T: Hash + Eq {
+    type Output = Self;
+    fn bitor(mut self, rhs: Self) -> Self {
+        for (item, count) in rhs.counts {
+            let entry = self.counts.entry(item).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+        self
+    }
+}
+
+/// Minimum of counts (multiset intersection).
+impl<T> BitAnd for Counter<T> where
+    T: Hash + Eq {
+    type Output = Self;
+    fn bitand(mut self, rhs: Self) -> Self {
+        self.counts.retain(|item, count| {
+            *count = (*count).min(rhs.get(item));
+            *count > 0
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Counter;
+
+    #[test]
+    fn test_counts() {
+        let mut counter: Counter<char> = "mississippi".chars().collect();
+        assert_eq!(counter.get(&'s'), 4);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 4);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common(2), [('i', 4), ('s', 4)]);
+
+        counter.sub(&'m');
+        counter.sub(&'m');
+        counter.add('p');
+        assert_eq!(counter.len(), 3);
+        assert_eq!(counter.most_common(5), [('i', 4), ('s', 4), ('p', 3)]);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Counter<u32> = [1, 1, 1, 2, 3].into_iter().collect();
+        let b: Counter<u32> = [1, 2, 2, 4].into_iter().collect();
+        let sorted = |c: Counter<u32>| c.most_common(usize::MAX);
+        assert_eq!(sorted(a.clone() + b.clone()), [(1, 4), (2, 3), (3, 1), (4, 1)]);
+        assert_eq!(sorted(a.clone() - b.clone()), [(1, 2), (3, 1)]);
+        assert_eq!(sorted(a.clone() | b.clone()), [(1, 3), (2, 2), (3, 1), (4, 1)]);
+        assert_eq!(sorted(a & b), [(1, 1), (2, 1)]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+/// A cyclic sequence over `0..k` of length `k^n` in which every length-`n` word appears
+/// exactly once as a (cyclic) window. Append the first `n - 1` symbols for a linear one.
+/// Built from an Eulerian circuit of the de Bruijn graph (Hierholzer). O(k^n).
+pub fn de_bruijn(k: usize, n: usize) -> Vec<usize> {
+    assert!(k > 0 && n > 0, "alphabet and word length must be positive");
+    // Nodes are the (n - 1)-symbol suffixes; the edge labeled d from v leads to v·d.
+    let nodes = k.pow(n as u32 - 1);
+    let mut next_label = vec![0; nodes];
+    let mut stack = vec![(0, None)];
+    let mut result = Vec::with_capacity(nodes * k);
+    while let Some(&(node, _)) = stack.last() {
+        if next_label[node] < k {
+            let label = next_label[node];
+            next_label[node] += 1;
+            stack.push(((node * k + label) % nodes, Some(label)));
+        } else if let (_, Some(label)) = stack.pop().unwrap() {
+            result.push(label);
+        }
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::de_bruijn;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_all_windows_once() {
+        for (k, n) in [(2, 1), (2, 3), (3, 2), (4, 3), (1, 4)] {
+            let sequence = de_bruijn(k, n);
+            assert_eq!(sequence.len(), k.pow(n as u32));
+            let windows: HashSet<Vec<usize>> = (0..sequence.len())
+                .map(|i| (0..n).map(|j| sequence[(i + j) % sequence.len()]).collect())
+                .collect();
+            assert_eq!(windows.len(), sequence.len());
+        }
+    }
+}
\ No newline at end of file
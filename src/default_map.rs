@@ -0,0 +1,87 @@
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
+
+use crate::fast_hash::FastHashMap;
+
+/// Hash map that behaves as if every key were present with `V::default()`,
+/// like Python's `defaultdict`. `map[k]` reads never insert; `&mut map[k]` does.
+#[derive(Clone)]
+pub struct DefaultMap<K, V> {
+    map: FastHashMap<K, V>,
+    default: V,
+}
+
+impl<K, V> DefaultMap<K, V> where
+    K: Hash + Eq,
+    V: Default {
+    pub fn new() -> Self {
+        Self { map: FastHashMap::default(), default: V::default() }
+    }
+
+    /// Number of keys actually stored.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> &V {
+        self.map.get(key).unwrap_or(&self.default)
+    }
+
+    pub fn get_mut(&mut self, key: K) -> &mut V {
+        self.map.entry(key).or_default()
+    }
+
+    pub fn remove(&mut self, key: &K) -> V {
+        self.map.remove(key).unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter()
+    }
+
+    pub fn into_inner(self) -> FastHashMap<K, V> {
+        self.map
+    }
+}
+
+impl<K, V> Default for DefaultMap<K, V> where
+    K: Hash + Eq,
+    V: Default {
+    fn default() -> Self { Self::new() }
+}
+
+impl<K, V> Index<K> for DefaultMap<K, V> where
+    K: Hash + Eq,
+    V: Default {
+    type Output = V;
+    fn index(&self, key: K) -> &V { self.get(&key) }
+}
+
+impl<K, V> IndexMut<K> for DefaultMap<K, V> where
+    K: Hash + Eq,
+    V: Default {
+    fn index_mut(&mut self, key: K) -> &mut V { self.get_mut(key) }
+}
+
+impl<K, V> FromIterator<(K, V)> for DefaultMap<K, V> where
+    K: Hash + Eq,
+    V: Default {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let map = iter.into_iter().collect();
+        Self { map, default: V::default() }
+    }
+}
+
+impl<K, V> IntoIterator for DefaultMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::collections::hash_map::IntoIter<K, V>;
+    fn into_iter(self) -> Self::IntoIter { self.map.into_iter() }
+}
\ No newline at end of file
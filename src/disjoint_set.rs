@@ -1,3 +1,7 @@
+use std::ops::RangeBounds;
+
+use crate::range::to_half_open;
+
 #[derive(Clone)]
 pub struct DisjointSet {
     parents: Vec<Option<usize>>,
@@ -26,6 +30,7 @@ impl DisjointSet {
     pub fn union(&mut self, u: usize, v: usize) -> usize {
         let rootu = self.find(u);
         let rootv = self.find(v);
+        if rootu == rootv { return rootu; }
         if self.ranks[rootu] < self.ranks[rootv] {
             self.parents[rootu] = Some(rootv);
             rootv
@@ -37,4 +42,211 @@ impl DisjointSet {
             rootu
         }
     }
+
+    /// Groups ordered by their smallest member, members in increasing order.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.parents.len();
+        let mut group_of_root = vec![None; n];
+        let mut result: Vec<Vec<usize>> = Vec::new();
+        for u in 0..n {
+            let root = self.find(u);
+            let idx = *group_of_root[root].get_or_insert_with(|| {
+                result.push(Vec::new());
+                result.len() - 1
+            });
+            result[idx].push(u);
+        }
+        result
+    }
+}
+
+/// Union by size without path compression, so unions can be undone.
+#[derive(Clone)]
+pub struct RollbackDisjointSet {
+    parents: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+    history: Vec<Option<(usize, usize)>>, // (child root, parent root), None for no-op unions
+}
+
+impl RollbackDisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: vec![None; n],
+            sizes: vec![1; n],
+            history: Vec::new(),
+        }
+    }
+
+    pub fn find(&self, mut u: usize) -> usize {
+        while let Some(parent) = self.parents[u] {
+            u = parent;
+        }
+        u
+    }
+
+    pub fn same(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    pub fn size(&self, u: usize) -> usize {
+        self.sizes[self.find(u)]
+    }
+
+    pub fn union(&mut self, u: usize, v: usize) -> usize {
+        let mut rootu = self.find(u);
+        let mut rootv = self.find(v);
+        if rootu == rootv {
+            self.history.push(None);
+            return rootu;
+        }
+        if self.sizes[rootu] < self.sizes[rootv] {
+            std::mem::swap(&mut rootu, &mut rootv);
+        }
+        self.parents[rootv] = Some(rootu);
+        self.sizes[rootu] += self.sizes[rootv];
+        self.history.push(Some((rootv, rootu)));
+        rootu
+    }
+
+    /// Reverts the latest `union` that has not been undone yet.
+    pub fn undo(&mut self) {
+        let last = self.history.pop().expect("no union to undo");
+        if let Some((child, parent)) = last {
+            self.parents[child] = None;
+            self.sizes[parent] -= self.sizes[child];
+        }
+    }
+}
+
+/// Indices `0..n` that can be erased, with "first remaining index at or after `i`" queries.
+/// Each erased index points past itself, and path compression keeps `next` amortized O(log n).
+#[derive(Clone)]
+pub struct IntervalDisjointSet {
+    next: Vec<usize>, // next[i] == i while i remains; next[n] == n is the sentinel
+}
+
+impl IntervalDisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self { next: (0..=n).collect() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The smallest remaining index `>= i`.
+    pub fn next(&mut self, i: usize) -> Option<usize> {
+        let mut root = i;
+        while self.next[root] != root {
+            root = self.next[root];
+        }
+        let mut u = i;
+        while self.next[u] != root {
+            u = std::mem::replace(&mut self.next[u], root);
+        }
+        if root < self.len() { Some(root) } else { None }
+    }
+
+    pub fn contains(&mut self, i: usize) -> bool {
+        self.next(i) == Some(i)
+    }
+
+    /// Returns whether `i` was still present.
+    pub fn erase(&mut self, i: usize) -> bool {
+        if !self.contains(i) { return false; }
+        self.next[i] = i + 1;
+        true
+    }
+
+    /// Erases every remaining index in `range`, calling `f` on each in increasing order.
+    pub fn erase_range(&mut self, range: impl RangeBounds<usize>, mut f: impl FnMut(usize)) {
+        let (begin, end) = to_half_open(range, self.len());
+        let mut i = begin;
+        while let Some(j) = self.next(i).filter(|&j| j < end) {
+            self.next[j] = j + 1;
+            f(j);
+            i = j + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_within_same_set() {
+        let mut dsu = DisjointSet::new(3);
+        let root = dsu.union(0, 1);
+        // A repeated union used to make `root` its own parent, looping `find` forever.
+        assert_eq!(dsu.union(1, 0), root);
+        assert_eq!(dsu.union(0, 0), root);
+        dsu.union(2, 0);
+        assert_eq!(dsu.find(2), dsu.find(1));
+    }
+
+    #[test]
+    fn test_union_and_find() {
+        let mut dsu = DisjointSet::new(5);
+        dsu.union(0, 3);
+        dsu.union(4, 3);
+        // Joining members of the same set again is a no-op.
+        dsu.union(0, 4);
+
+        assert_eq!(dsu.find(0), dsu.find(4));
+        assert_ne!(dsu.find(0), dsu.find(1));
+        assert_ne!(dsu.find(1), dsu.find(2));
+    }
+
+    #[test]
+    fn test_groups_order() {
+        let mut dsu = DisjointSet::new(6);
+        dsu.union(5, 2);
+        dsu.union(4, 1);
+        dsu.union(1, 5);
+
+        assert_eq!(dsu.groups(), vec![vec![0], vec![1, 2, 4, 5], vec![3]]);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut dsu = RollbackDisjointSet::new(4);
+        dsu.union(0, 1);
+        dsu.union(2, 3);
+        dsu.union(1, 0);
+        dsu.union(1, 3);
+        assert_eq!(dsu.size(0), 4);
+
+        dsu.undo();
+        assert!(!dsu.same(0, 2));
+        assert_eq!(dsu.size(3), 2);
+        // The no-op union is undone as well.
+        dsu.undo();
+        dsu.undo();
+        assert!(dsu.same(0, 1));
+        assert!(!dsu.same(2, 3));
+    }
+
+    #[test]
+    fn test_interval_disjoint_set() {
+        let mut seats = IntervalDisjointSet::new(8);
+        assert!(seats.erase(3));
+        assert!(!seats.erase(3));
+        assert_eq!(seats.next(3), Some(4));
+
+        let mut taken = Vec::new();
+        seats.erase_range(2..6, |i| taken.push(i));
+        assert_eq!(taken, [2, 4, 5]);
+        assert_eq!(seats.next(1), Some(1));
+        assert_eq!(seats.next(2), Some(6));
+        assert!(!seats.contains(5));
+
+        seats.erase_range(.., |_| ());
+        assert_eq!(seats.next(0), None);
+        assert_eq!(seats.next(8), None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::fenwick::PointFenwickTree;
+
+/// Number of distinct values in each `values[range]`, answered offline by sweeping
+/// right ends and marking only the last occurrence of each value. O((n + q) log n).
+pub fn count_distinct_in_ranges<T>(values: &[T], ranges: &[Range<usize>]) -> Vec<usize> where
+    T: Hash + Eq {
+    let mut order: Vec<usize> = (0..ranges.len()).collect();
+    order.sort_unstable_by_key(|&q| ranges[q].end);
+    let mut marks = PointFenwickTree::<isize>::new(values.len());
+    let mut last_seen = HashMap::new();
+    let mut result = vec![0; ranges.len()];
+    let mut swept = 0;
+    for q in order {
+        while swept < ranges[q].end {
+            if let Some(prev) = last_seen.insert(&values[swept], swept) {
+                marks.add(prev, -1);
+            }
+            marks.add(swept, 1);
+            swept += 1;
+        }
+        result[q] = marks.sum(ranges[q].clone()) as usize;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_count_distinct_in_ranges() {
+        let values = [1, 2, 1, 3, 2, 2];
+        let ranges = [0..6, 0..1, 1..3, 2..5, 4..6, 3..3];
+        assert_eq!(count_distinct_in_ranges(&values, &ranges), [3, 1, 2, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(17);
+        for n in 1..=30 {
+            let values: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n / 3 + 1)).collect();
+            let ranges: Vec<_> = (0..50)
+                .map(|_| {
+                    let begin = rng.gen_range(0..n + 1);
+                    begin..rng.gen_range(begin..n + 1)
+                })
+                .collect();
+            let expected: Vec<usize> = ranges.iter().map(|r| values[r.clone()].iter().collect::<HashSet<_>>().len()).collect();
+            assert_eq!(count_distinct_in_ranges(&values, &ranges), expected);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,158 @@
+/// Row minima of `cost(i, j)` for `i < rows`, `j < cols` with their leftmost argmin,
+/// assuming the argmins are non-decreasing in `i` (as for Monge costs). O((rows + cols) log rows).
+pub fn monotone_minima(rows: usize, cols: usize, cost: impl Fn(usize, usize) -> i64) -> Vec<(i64, usize)> {
+    let mut result = vec![(i64::MAX, 0); rows];
+    if cols > 0 {
+        solve(0, rows, 0, cols - 1, &cost, &mut result);
+    }
+    result
+}
+
+fn solve(
+    begin: usize,
+    end: usize,
+    opt_begin: usize,
+    opt_last: usize,
+    cost: &impl Fn(usize, usize) -> i64,
+    result: &mut [(i64, usize)],
+) {
+    if begin >= end { return; }
+    let mid = (begin + end) / 2;
+    let mut best = (i64::MAX, opt_begin);
+    for j in opt_begin..=opt_last {
+        let value = cost(mid, j);
+        if value < best.0 {
+            best = (value, j);
+        }
+    }
+    result[mid] = best;
+    solve(begin, mid, opt_begin, best.1, cost, result);
+    solve(mid + 1, end, best.1, opt_last, cost, result);
+}
+
+/// One layer of `next[i] = min_{j < i} prev[j] + cost(j, i)`, with `i64::MAX` as infinity
+/// (so `next[0]` is infinite). `cost` should satisfy the quadrangle inequality.
+pub fn dp_layer(prev: &[i64], cost: impl Fn(usize, usize) -> i64) -> Vec<i64> {
+    let n = prev.len();
+    let minima = monotone_minima(n, n, |i, j| {
+        if j >= i || prev[j] == i64::MAX { return i64::MAX; }
+        prev[j] + cost(j, i)
+    });
+    minima.into_iter().map(|(value, _)| value).collect()
+}
+
+/// `dp[i][j] = min_{i < k < j} dp[i][k] + dp[k][j] + cost(i, j)` over boundaries `0..=n`,
+/// with `dp[i][i + 1] = 0`. `cost` must satisfy the quadrangle inequality and be monotone
+/// on interval inclusion. O(n^2).
+pub fn knuth_interval_dp(n: usize, cost: impl Fn(usize, usize) -> i64) -> Vec<Vec<i64>> {
+    let mut dp = vec![vec![0; n + 1]; n + 1];
+    let mut opt = vec![vec![0; n + 1]; n + 1];
+    for i in 0..n {
+        opt[i][i + 1] = i + 1;
+    }
+    for len in 2..=n {
+        for i in 0..=n - len {
+            let j = i + len;
+            let first = opt[i][j - 1].max(i + 1);
+            let last = opt[i + 1][j].min(j - 1);
+            let (value, k) = (first..=last).map(|k| (dp[i][k] + dp[k][j], k)).min().unwrap();
+            dp[i][j] = value + cost(i, j);
+            opt[i][j] = k;
+        }
+    }
+    dp
+}
+
+/// Optimum with exactly `k` parts, given that it is convex in `k` and that
+/// `solve(penalty)` returns the minimum of `value + penalty * parts` together with
+/// the smallest `parts` attaining it. Penalties are searched in `lo..=hi`.
+pub fn aliens(k: usize, lo: i64, hi: i64, mut solve: impl FnMut(i64) -> (i64, usize)) -> i64 {
+    let (mut lo, mut hi) = (lo, hi);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if solve(mid).1 <= k {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    let (value, _) = solve(lo);
+    value - lo * k as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_into_groups() {
+        // Split a into k contiguous groups minimizing the sum of squared group sums.
+        let a: [i64; 10] = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let n = a.len();
+        let mut prefix = vec![0; n + 1];
+        for i in 0..n {
+            prefix[i + 1] = prefix[i] + a[i];
+        }
+        let cost = |j: usize, i: usize| (prefix[i] - prefix[j]).pow(2);
+
+        let mut dp = vec![i64::MAX; n + 1];
+        dp[0] = 0;
+        let mut naive = dp.clone();
+        for _ in 0..4 {
+            dp = dp_layer(&dp, cost);
+            naive = (0..=n)
+                .map(|i| {
+                    let reachable = (0..i).filter(|&j| naive[j] != i64::MAX);
+                    reachable.map(|j| naive[j] + cost(j, i)).min().unwrap_or(i64::MAX)
+                })
+                .collect();
+            assert_eq!(dp, naive);
+        }
+    }
+
+    #[test]
+    fn test_monotone_minima() {
+        let xs: [i64; 4] = [0, 3, 7, 12];
+        let ys: [i64; 5] = [1, 2, 6, 10, 13];
+        let minima = monotone_minima(xs.len(), ys.len(), |i, j| (xs[i] - ys[j]).pow(2));
+        assert_eq!(minima, vec![(1, 0), (1, 1), (1, 2), (1, 4)]);
+    }
+
+    #[test]
+    fn test_optimal_merge() {
+        let a: [i64; 7] = [4, 1, 7, 3, 3, 9, 2];
+        let n = a.len();
+        let mut prefix = vec![0; n + 1];
+        for i in 0..n {
+            prefix[i + 1] = prefix[i] + a[i];
+        }
+        let cost = |i: usize, j: usize| prefix[j] - prefix[i];
+        let dp = knuth_interval_dp(n, cost);
+
+        let mut naive = vec![vec![0; n + 1]; n + 1];
+        for len in 2..=n {
+            for i in 0..=n - len {
+                let j = i + len;
+                let best = (i + 1..j).map(|k| naive[i][k] + naive[k][j]).min().unwrap();
+                naive[i][j] = best + cost(i, j);
+            }
+        }
+        assert_eq!(dp, naive);
+    }
+
+    #[test]
+    fn test_aliens_choose_exactly_k() {
+        // Pick exactly k elements minimizing their sum.
+        let a: [i64; 6] = [-5, 3, -2, 7, -9, 0];
+        let solve = |penalty: i64| {
+            let taken = a.iter().map(|&x| x + penalty).filter(|&x| x < 0);
+            (taken.clone().sum(), taken.count())
+        };
+        let mut sorted = a.to_vec();
+        sorted.sort_unstable();
+        for k in 0..=a.len() {
+            let expected: i64 = sorted[..k].iter().sum();
+            assert_eq!(aliens(k, -20, 20, solve), expected, "k = {}", k);
+        }
+    }
+}
\ No newline at end of file
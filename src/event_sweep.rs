@@ -0,0 +1,72 @@
+/// Sweep-line skeleton: collect `(coordinate, payload)` events, then visit them
+/// in coordinate order, one batch per distinct coordinate.
+#[derive(Clone)]
+pub struct EventSweep<C, P> {
+    events: Vec<(C, P)>,
+}
+
+impl<C, P> EventSweep<C, P> where
+    C: Ord {
+    pub fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn push(&mut self, coordinate: C, payload: P) {
+        self.events.push((coordinate, payload));
+    }
+
+    /// Calls `process` once per distinct coordinate, in increasing order, with the
+    /// payloads at that coordinate in insertion order.
+    pub fn run(mut self, mut process: impl FnMut(&C, &[P])) {
+        self.events.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut batch = Vec::new();
+        let mut current: Option<C> = None;
+        for (coordinate, payload) in self.events {
+            if current.as_ref().is_some_and(|c| *c != coordinate) {
+                process(current.as_ref().unwrap(), &batch);
+                batch.clear();
+            }
+            current = Some(coordinate);
+            batch.push(payload);
+        }
+        if let Some(c) = current {
+            process(&c, &batch);
+        }
+    }
+}
+
+impl<C, P> Default for EventSweep<C, P> where
+    C: Ord {
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EventSweep;
+
+    #[test]
+    fn test_max_overlap() {
+        // Half-open intervals: ends at a coordinate are processed with starts there.
+        let intervals = [(1, 5), (2, 6), (5, 8), (6, 7), (9, 10)];
+        let mut sweep = EventSweep::new();
+        for &(l, r) in &intervals {
+            sweep.push(l, 1);
+            sweep.push(r, -1);
+        }
+        let mut active = 0;
+        let mut history = Vec::new();
+        sweep.run(|&x, deltas| {
+            active += deltas.iter().sum::<i32>();
+            history.push((x, active));
+        });
+        assert_eq!(history, [(1, 1), (2, 2), (5, 2), (6, 2), (7, 1), (8, 0), (9, 1), (10, 0)]);
+    }
+}
\ No newline at end of file
@@ -1,3 +1,10 @@
+use std::ops::RangeBounds;
+
+use crate::cast::CheckedCast;
+use crate::modint::Mod;
+use crate::range::to_half_open;
+use crate::rng::XorShift64;
+
 pub trait FenwickCompatible: Copy {
     /// x + zero() == x
     fn zero() -> Self;
@@ -12,134 +19,491 @@ pub trait FenwickCompatible: Copy {
     fn add_assign(&mut self, rhs: Self) { *self = self.add(rhs) }
 }
 
-impl FenwickCompatible for isize {
-    fn zero() -> Self { 0 }
+macro_rules! impl_fenwick_compatible_signed {
+    ($($t:ty),*) => {$(
+        impl FenwickCompatible for $t {
+            fn zero() -> Self { 0 }
+            fn neg(self) -> Self { -self }
+            fn add(self, rhs: Self) -> Self { self + rhs }
+            fn sub(self, rhs: Self) -> Self { self - rhs }
+            fn scale(self, n: usize) -> Self { self * (n as $t) }
+        }
+    )*};
+}
+
+impl_fenwick_compatible_signed!(i32, i64, isize);
+
+/// Unsigned values work modulo 2^BITS, so negative intermediates wrap around and any
+/// sum whose true value is representable comes out exact.
+macro_rules! impl_fenwick_compatible_unsigned {
+    ($($t:ty),*) => {$(
+        impl FenwickCompatible for $t {
+            fn zero() -> Self { 0 }
+            fn neg(self) -> Self { self.wrapping_neg() }
+            fn add(self, rhs: Self) -> Self { self.wrapping_add(rhs) }
+            fn sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+            fn scale(self, n: usize) -> Self { self.wrapping_mul(n as $t) }
+        }
+    )*};
+}
+
+impl_fenwick_compatible_unsigned!(u32, u64, usize);
+
+impl<const N: usize> FenwickCompatible for Mod<N> {
+    fn zero() -> Self { Mod::new(0) }
     fn neg(self) -> Self { -self }
     fn add(self, rhs: Self) -> Self { self + rhs }
     fn sub(self, rhs: Self) -> Self { self - rhs }
-    fn scale(self, n: usize) -> Self { self * (n as isize) }
+    fn scale(self, n: usize) -> Self { self * Mod::new(n) }
 }
 
 #[derive(Clone)]
 pub struct FenwickTree<T> where 
     T: FenwickCompatible {
-    diffs: PrimitiveFenwickTree<T>,
-    offsets: PrimitiveFenwickTree<T>,
+    diffs: PointFenwickTree<T>,
+    offsets: PointFenwickTree<T>,
 }
 
 impl<T> FenwickTree<T> where 
     T: FenwickCompatible {
     pub fn new(size: usize) -> Self {
-        let diffs = PrimitiveFenwickTree::new(size);
-        let offsets = PrimitiveFenwickTree::new(size);
+        let diffs = PointFenwickTree::new(size);
+        let offsets = PointFenwickTree::new(size);
+        Self { diffs, offsets }
+    }
+
+    /// Builds the tree over `values` in O(n).
+    pub fn from_slice(values: &[T]) -> Self {
+        // Equivalent to `add(i..=i, values[i])` for every `i`.
+        let diff_values: Vec<T> = (0..values.len())
+            .map(|i| if i == 0 { values[0] } else { values[i].sub(values[i - 1]) })
+            .collect();
+        let offset_values: Vec<T> = diff_values.iter().enumerate().map(|(i, d)| d.scale(i).neg()).collect();
+        let diffs = PointFenwickTree::from_slice(&diff_values);
+        let offsets = PointFenwickTree::from_slice(&offset_values);
         Self { diffs, offsets }
     }
 
-    pub fn add(&mut self, begin: usize, end: usize, val: T) {
+    pub fn add(&mut self, range: impl RangeBounds<usize>, val: T) {
+        let (begin, end) = to_half_open(range, self.len());
         if begin >= end { return; }
         self.diffs.add(begin, val);
-        self.diffs.add(end, val.neg());
         self.offsets.add(begin, val.scale(begin).neg());
-        self.offsets.add(end, val.scale(end));
+        if end < self.len() {
+            self.diffs.add(end, val.neg());
+            self.offsets.add(end, val.scale(end));
+        }
     }
 
-    pub fn sum(&self, begin: usize, end: usize) -> T {
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.len());
         if begin >= end { return T::zero(); }
         self.sum_until(end).sub(self.sum_until(begin))
     }
 
+    pub fn get(&self, idx: usize) -> T {
+        self.sum(idx..=idx)
+    }
+
+    pub fn set(&mut self, idx: usize, val: T) {
+        let delta = val.sub(self.get(idx));
+        self.add(idx..=idx, delta);
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        (0..self.len()).map(|i| self.sum(i..=i)).collect()
+    }
+
+    fn len(&self) -> usize {
+        self.diffs.tree.len()
+    }
+
     fn sum_until(&self, end: usize) -> T {
-        if end == 0 { return T::zero(); }
-        let sum = self.diffs.sum(end - 1);
-        let offset = self.offsets.sum(end - 1);
+        let sum = self.diffs.prefix_sum(end);
+        let offset = self.offsets.prefix_sum(end);
         sum.scale(end).add(offset)
     }
 }
 
+impl<T> FenwickTree<T> where
+    T: FenwickCompatible + PartialOrd {
+    /// With nonnegative values, the index `i` such that `sum(..i) <= x < sum(..=i)`,
+    /// or the length if `x >= sum(..)`.
+    pub fn select_by_prefix(&self, x: T) -> usize {
+        self.partition_point(|sum| sum <= x)
+    }
+
+    /// With nonnegative values, the smallest `i` with `sum(..=i) >= target`, or the length
+    /// if there is none. For 0/1 counts this is the position of the `target`-th one (1-indexed).
+    pub fn lower_bound(&self, target: T) -> usize {
+        self.partition_point(|sum| sum < target)
+    }
+
+    /// Largest `p` with `pred(sum(..p))`, assuming `pred` holds for a prefix of the
+    /// positions `0..=len` (as for nondecreasing prefix sums). O(log n).
+    pub fn partition_point(&self, pred: impl Fn(T) -> bool) -> usize {
+        let len = self.len();
+        let mut pos = 0;
+        let mut diff = T::zero();
+        let mut offset = T::zero();
+        let mut step = if len == 0 { 0 } else { 1 << len.ilog2() };
+        while step > 0 {
+            let next = pos + step;
+            if next <= len {
+                let next_diff = diff.add(self.diffs.tree[next - 1]);
+                let next_offset = offset.add(self.offsets.tree[next - 1]);
+                if pred(next_diff.scale(next).add(next_offset)) {
+                    pos = next;
+                    diff = next_diff;
+                    offset = next_offset;
+                }
+            }
+            step /= 2;
+        }
+        pos
+    }
+}
+
+impl<T> FenwickTree<T> where
+    T: FenwickCompatible + PartialOrd + CheckedCast + TryFrom<u64> {
+    /// Picks an index with probability proportional to its (nonnegative) value.
+    pub fn sample(&self, rng: &mut XorShift64) -> usize {
+        let total = self.sum(..);
+        assert!(total > T::zero(), "cannot sample from a nonpositive total weight");
+        let x = T::try_from(rng.gen_below(total.as_u64())).ok().expect("sample below total fits in T");
+        self.select_by_prefix(x)
+    }
+}
+
+impl<T> FromIterator<T> for FenwickTree<T> where
+    T: FenwickCompatible {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        Self::from_slice(&values)
+    }
+}
+
+/// Point add and range sum in O(log n).
 #[derive(Clone)]
-struct PrimitiveFenwickTree<T> where 
+pub struct PointFenwickTree<T> where
     T: FenwickCompatible {
     tree: Vec<T>
 }
 
-impl<T> PrimitiveFenwickTree<T> where 
+impl<T> PointFenwickTree<T> where
     T: FenwickCompatible {
-    fn new(size: usize) -> Self {
+    pub fn new(size: usize) -> Self {
         let tree = vec![T::zero(); size];
         Self { tree }
     }
 
-    fn add(&mut self, mut idx: usize, val: T) {
-        idx += 1;
+    /// Builds the tree over `values` in O(n).
+    pub fn from_slice(values: &[T]) -> Self {
+        let mut tree = values.to_vec();
+        for i in 1..=tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= tree.len() {
+                let val = tree[i - 1];
+                tree[parent - 1].add_assign(val);
+            }
+        }
+        Self { tree }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn add(&mut self, idx: usize, val: T) {
+        assert!(idx < self.len(), "index {} out of range for size {}", idx, self.len());
+        let mut idx = idx + 1;
         while idx <= self.tree.len() {
             self.tree[idx - 1].add_assign(val);
             idx += idx & idx.wrapping_neg();
         }
     }
 
-    fn sum(&self, mut idx: usize) -> T {
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.len());
+        self.prefix_sum(end).sub(self.prefix_sum(begin))
+    }
+
+    pub fn get(&self, idx: usize) -> T {
+        self.sum(idx..=idx)
+    }
+
+    pub fn set(&mut self, idx: usize, val: T) {
+        let delta = val.sub(self.get(idx));
+        self.add(idx, delta);
+    }
+
+    /// Largest `p` with `pred(sum(..p))`, assuming `pred` holds for a prefix of the
+    /// positions `0..=len`. O(log n).
+    pub fn partition_point(&self, pred: impl Fn(T) -> bool) -> usize {
+        let len = self.len();
+        let mut pos = 0;
+        let mut sum = T::zero();
+        let mut step = if len == 0 { 0 } else { 1 << len.ilog2() };
+        while step > 0 {
+            let next = pos + step;
+            if next <= len {
+                let next_sum = sum.add(self.tree[next - 1]);
+                if pred(next_sum) {
+                    pos = next;
+                    sum = next_sum;
+                }
+            }
+            step /= 2;
+        }
+        pos
+    }
+
+    /// Sum over `..end`.
+    fn prefix_sum(&self, mut end: usize) -> T {
         let mut result = T::zero();
-        idx += 1;
-        while idx > 0 {
-            result.add_assign(self.tree[idx - 1]);
-            idx -= idx & idx.wrapping_neg();
+        while end > 0 {
+            result.add_assign(self.tree[end - 1]);
+            end -= end & end.wrapping_neg();
         }
         result
     }
 }
 
+impl<T> PointFenwickTree<T> where
+    T: FenwickCompatible + PartialOrd {
+    /// With nonnegative values, the smallest `i` with `sum(..=i) >= target`, or the length.
+    pub fn lower_bound(&self, target: T) -> usize {
+        self.partition_point(|sum| sum < target)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::naive::RangeArray;
 
     #[test]
     fn test_empty() {
         let ft = FenwickTree::<isize>::new(0);
-        assert_eq!(ft.sum(0, 0), 0);
+        assert_eq!(ft.sum(0..0), 0);
     }
 
     #[test]
     fn test_single_update() {
         let mut ft = FenwickTree::<isize>::new(10);
-        assert_eq!(ft.sum(0, 10), 0);
+        assert_eq!(ft.sum(0..10), 0);
 
-        ft.add(0, 5, 10);
-        assert_eq!(ft.sum(0, 5), 10 * 5);
-        assert_eq!(ft.sum(0, 10), 10 * 5);
-        assert_eq!(ft.sum(5, 10), 0);
+        ft.add(0..5, 10);
+        assert_eq!(ft.sum(0..5), 10 * 5);
+        assert_eq!(ft.sum(0..10), 10 * 5);
+        assert_eq!(ft.sum(5..10), 0);
+        assert_eq!(ft.to_vec(), vec![10, 10, 10, 10, 10, 0, 0, 0, 0, 0]);
     }
 
     #[test]
     fn test_multiple_updates() {
         let mut ft = FenwickTree::<isize>::new(10);
 
-        ft.add(0, 3, 5);
-        ft.add(2, 6, 4);
-        ft.add(5, 10, 1);
+        ft.add(0..3, 5);
+        ft.add(2..6, 4);
+        ft.add(5..10, 1);
 
-        assert_eq!(ft.sum(0, 1), 5);
-        assert_eq!(ft.sum(0, 2), 5 + 5);
-        assert_eq!(ft.sum(0, 3), 5 + 5 + 9);
-        assert_eq!(ft.sum(0, 5), 5 + 5 + 9 + 4 + 4);
-        assert_eq!(ft.sum(0, 6), 5 + 5 + 9 + 4 + 4 + 5);
-        assert_eq!(ft.sum(0, 10), 5 + 5 + 9 + 4 + 4 + 5 + 1 + 1 + 1 + 1);
+        assert_eq!(ft.sum(0..1), 5);
+        assert_eq!(ft.sum(0..2), 5 + 5);
+        assert_eq!(ft.sum(0..3), 5 + 5 + 9);
+        assert_eq!(ft.sum(0..5), 5 + 5 + 9 + 4 + 4);
+        assert_eq!(ft.sum(0..6), 5 + 5 + 9 + 4 + 4 + 5);
+        assert_eq!(ft.sum(0..10), 5 + 5 + 9 + 4 + 4 + 5 + 1 + 1 + 1 + 1);
 
-        assert_eq!(ft.sum(2, 6), 22);
-        assert_eq!(ft.sum(5, 8), 7);
+        assert_eq!(ft.sum(2..6), 22);
+        assert_eq!(ft.sum(5..8), 7);
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn test_negative_values() {
         let mut ft = FenwickTree::<isize>::new(5);
 
-        ft.add(0, 5, 2);
-        ft.add(1, 4, -3);
+        ft.add(0..5, 2);
+        ft.add(1..4, -3);
+
+        assert_eq!(ft.sum(0..1), 2);
+        assert_eq!(ft.sum(0..2), 2 + (-1));
+        assert_eq!(ft.sum(0..3), 2 + (-1) + (-1));
+        assert_eq!(ft.sum(0..4), 2 + (-1) + (-1) + (-1));
+        assert_eq!(ft.sum(0..5), 2 + (-1) + (-1) + (-1) + 2);
+        assert_eq!(ft.sum(1..4), -3);
+    }
+
+    #[test]
+    fn test_collect() {
+        let ft: FenwickTree<isize> = vec![3, -1, 4, 1, -5].into_iter().collect();
+
+        assert_eq!(ft.sum(..), 2);
+        assert_eq!(ft.sum(0..1), 3);
+        assert_eq!(ft.sum(1..4), 4);
+        assert_eq!(ft.sum(4..), -5);
+    }
+
+    #[test]
+    fn test_select_by_prefix() {
+        let mut ft: FenwickTree<isize> = vec![2, 0, 3, 1].into_iter().collect();
+
+        assert_eq!(ft.select_by_prefix(0), 0);
+        assert_eq!(ft.select_by_prefix(1), 0);
+        // index 1 has zero weight and is never selected
+        assert_eq!(ft.select_by_prefix(2), 2);
+        assert_eq!(ft.select_by_prefix(4), 2);
+        assert_eq!(ft.select_by_prefix(5), 3);
+        assert_eq!(ft.select_by_prefix(6), 4);
+
+        // values: [3, 1, 4, 2]
+        ft.add(0..3, 1);
+        ft.add(3.., 1);
+        assert_eq!(ft.select_by_prefix(3), 1);
+        assert_eq!(ft.select_by_prefix(7), 2);
+        assert_eq!(ft.select_by_prefix(8), 3);
+    }
+
+    #[test]
+    fn test_sample() {
+        let ft: FenwickTree<isize> = vec![1, 0, 3].into_iter().collect();
+        let mut rng = XorShift64::new(7);
+        let mut counts = [0; 3];
+        for _ in 0..4000 {
+            counts[ft.sample(&mut rng)] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > 2 * counts[0]);
+
+        let ft: FenwickTree<u64> = vec![0, 5, 0, 5].into_iter().collect();
+        let ft_signed: FenwickTree<i64> = vec![0, 5, 0, 5].into_iter().collect();
+        let mut counts = [0; 4];
+        for _ in 0..1000 {
+            let i = ft.sample(&mut rng);
+            counts[i] += 1;
+            assert!(ft_signed.sample(&mut rng) % 2 == 1);
+        }
+        assert_eq!(counts[0] + counts[2], 0);
+        assert!(counts[1] > 400 && counts[3] > 400);
+    }
+
+    #[test]
+    #[should_panic(expected = "nonpositive total weight")]
+    fn test_sample_empty_weight_panics() {
+        FenwickTree::<u32>::new(3).sample(&mut XorShift64::new(1));
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let mut ft = FenwickTree::<isize>::new(6);
+
+        ft.add(.., 1);
+        ft.add(2..=3, 10);
+        ft.add(4.., 100);
+
+        // values: [1, 1, 11, 11, 101, 101]
+        assert_eq!(ft.sum(..), 226);
+        assert_eq!(ft.sum(..2), 2);
+        assert_eq!(ft.sum(2..=3), 22);
+        assert_eq!(ft.sum(3..), 213);
+    }
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(42);
+        for size in 1..=20 {
+            let mut ft = FenwickTree::<isize>::new(size);
+            let mut naive = RangeArray::new(vec![0; size]);
+            for _ in 0..200 {
+                let begin = rng.gen_range(0..size + 1);
+                let end = rng.gen_range(begin..size + 1);
+                if rng.next_u64() % 2 == 0 {
+                    let val = rng.gen_range(0..201) as isize - 100;
+                    ft.add(begin..end, val);
+                    naive.add(begin..end, val);
+                } else {
+                    assert_eq!(ft.sum(begin..end), naive.sum(begin..end));
+                }
+            }
+            assert_eq!(ft.to_vec(), naive.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_point_fenwick_tree() {
+        let mut ft = PointFenwickTree::<isize>::new(6);
+        for (i, val) in [3, -1, 4, 1, -5, 9].into_iter().enumerate() {
+            ft.add(i, val);
+        }
+        assert_eq!(ft.sum(..), 11);
+        assert_eq!(ft.sum(..3), 6);
+        assert_eq!(ft.sum(2..=4), 0);
+        ft.add(4, 5);
+        assert_eq!(ft.sum(3..), 10);
+        assert_eq!(ft.sum(4..4), 0);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let values = [3, -1, 4, 1, -5, 9, 2];
+        let ft = FenwickTree::<isize>::from_slice(&values);
+        assert_eq!(ft.to_vec(), values);
+        assert_eq!(ft.sum(2..6), 9);
+        let point = PointFenwickTree::<isize>::from_slice(&values);
+        for begin in 0..values.len() {
+            for end in begin..=values.len() {
+                assert_eq!(point.sum(begin..end), values[begin..end].iter().sum::<isize>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        // Multiset {1, 3, 3, 6} as counts.
+        let counts = [0, 1, 0, 2, 0, 0, 1];
+        let ft = FenwickTree::<isize>::from_slice(&counts);
+        let point = PointFenwickTree::<isize>::from_slice(&counts);
+        let kth: Vec<_> = (1..=5).map(|k| ft.lower_bound(k)).collect();
+        assert_eq!(kth, [1, 3, 3, 6, 7]);
+        assert_eq!(ft.partition_point(|sum| sum <= 1), 3);
+        assert_eq!(point.lower_bound(4), 6);
+        assert_eq!(point.partition_point(|sum| sum <= 4), 7);
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut ft = FenwickTree::<isize>::from_slice(&[5, 2, 7]);
+        ft.add(.., 1);
+        assert_eq!(ft.get(1), 3);
+        ft.set(1, -4);
+        assert_eq!(ft.to_vec(), [6, -4, 8]);
+        assert_eq!(ft.sum(..), 10);
+
+        let mut point = PointFenwickTree::<isize>::from_slice(&[5, 2, 7]);
+        point.set(2, 0);
+        assert_eq!(point.get(2), 0);
+        assert_eq!(point.sum(..), 7);
+    }
+
+    #[test]
+    fn test_unsigned_and_mod_elements() {
+        let mut ft = FenwickTree::<u64>::from_slice(&[5, 0, 3, 8]);
+        ft.add(1..3, 2);
+        assert_eq!(ft.to_vec(), [5, 2, 5, 8]);
+        assert_eq!(ft.sum(1..), 15);
+        ft.set(3, 1);
+        assert_eq!(ft.sum(2..), 6);
 
-        assert_eq!(ft.sum(0, 1), 2);
-        assert_eq!(ft.sum(0, 2), 2 + (-1));
-        assert_eq!(ft.sum(0, 3), 2 + (-1) + (-1));
-        assert_eq!(ft.sum(0, 4), 2 + (-1) + (-1) + (-1));
-        assert_eq!(ft.sum(0, 5), 2 + (-1) + (-1) + (-1) + 2);
-        assert_eq!(ft.sum(1, 4), -3);
+        type M = Mod<998_244_353>;
+        let mut ft = FenwickTree::<M>::new(4);
+        ft.add(.., M::new(998_244_352));
+        ft.add(2.., M::new(3));
+        assert_eq!(ft.sum(..).value, 2);
+        assert_eq!(ft.get(3).value, 2);
     }
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+/// Fixed-capacity ring buffer deque. Capacity is rounded up to a power of two so
+/// wrapping is a mask; pushing beyond it panics instead of reallocating.
+#[derive(Clone)]
+pub struct FlatDeque<T> {
+    buffer: Vec<T>,
+    mask: usize,
+    head: usize,
+    len: usize,
+}
+
+impl<T> FlatDeque<T> where
+    T: Copy + Default {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let buffer = vec![T::default(); capacity.max(1).next_power_of_two()];
+        let mask = buffer.len() - 1;
+        Self { buffer, mask, head: 0, len: 0 }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        assert!(self.len < self.buffer.len(), "FlatDeque capacity {} exceeded", self.buffer.len());
+        self.buffer[(self.head + self.len) & self.mask] = value;
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        assert!(self.len < self.buffer.len(), "FlatDeque capacity {} exceeded", self.buffer.len());
+        self.head = self.head.wrapping_sub(1) & self.mask;
+        self.buffer[self.head] = value;
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 { return None; }
+        let value = self.buffer[self.head];
+        self.head = (self.head + 1) & self.mask;
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 { return None; }
+        self.len -= 1;
+        Some(self.buffer[(self.head + self.len) & self.mask])
+    }
+
+    pub fn front(&self) -> Option<T> {
+        if self.len == 0 { return None; }
+        Some(self.buffer[self.head])
+    }
+
+    pub fn back(&self) -> Option<T> {
+        if self.len == 0 { return None; }
+        Some(self.buffer[(self.head + self.len - 1) & self.mask])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::FlatDeque;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_random_against_vec_deque() {
+        let mut rng = XorShift64::new(9);
+        let mut deque = FlatDeque::with_capacity(10);
+        let mut expected = VecDeque::new();
+        assert_eq!(deque.capacity(), 16);
+        for step in 0..2000 {
+            match rng.gen_range(0..4) {
+                0 if expected.len() < 16 => {
+                    deque.push_back(step);
+                    expected.push_back(step);
+                }
+                1 if expected.len() < 16 => {
+                    deque.push_front(step);
+                    expected.push_front(step);
+                }
+                2 => assert_eq!(deque.pop_front(), expected.pop_front()),
+                _ => assert_eq!(deque.pop_back(), expected.pop_back()),
+            }
+            assert_eq!(deque.len(), expected.len());
+            assert_eq!(deque.front(), expected.front().copied());
+            assert_eq!(deque.back(), expected.back().copied());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity 2 exceeded")]
+    fn test_overflow_panics() {
+        let mut deque = FlatDeque::with_capacity(2);
+        deque.push_back(1);
+        deque.push_front(0);
+        deque.push_back(2);
+    }
+}
\ No newline at end of file
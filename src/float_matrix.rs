@@ -0,0 +1,119 @@
+use crate::monoid::pow_monoid;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Summation {
+    Naive,
+    /// Compensated summation; slower, but keeps long dot products accurate.
+    Kahan,
+}
+
+fn dot(terms: impl Iterator<Item = f64>, summation: Summation) -> f64 {
+    match summation {
+        Summation::Naive => terms.sum(),
+        Summation::Kahan => {
+            let (mut sum, mut compensation) = (0.0, 0.0);
+            for term in terms {
+                let y = term - compensation;
+                let t = sum + y;
+                compensation = (t - sum) - y;
+                sum = t;
+            }
+            sum
+        }
+    }
+}
+
+pub fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect()
+}
+
+pub fn mat_mul(a: &[Vec<f64>], b: &[Vec<f64>], summation: Summation) -> Vec<Vec<f64>> {
+    let cols = b.first().map_or(0, |row| row.len());
+    a.iter()
+        .map(|row| (0..cols).map(|j| dot(row.iter().zip(b).map(|(&x, b_row)| x * b_row[j]), summation)).collect())
+        .collect()
+}
+
+/// `a^exp` by repeated squaring. With `renormalize`, every product has its rows rescaled
+/// to sum to 1, which keeps powers of a row-stochastic matrix from drifting.
+pub fn mat_pow(a: &[Vec<f64>], exp: u64, summation: Summation, renormalize: bool) -> Vec<Vec<f64>> {
+    pow_monoid(a.to_vec(), exp, identity(a.len()), |x, y| {
+        let mut product = mat_mul(x, y, summation);
+        if renormalize {
+            for row in &mut product {
+                let total: f64 = row.iter().sum();
+                if total != 0.0 {
+                    row.iter_mut().for_each(|v| *v /= total);
+                }
+            }
+        }
+        product
+    })
+}
+
+/// Stationary distribution `pi` with `pi p = pi` and `sum(pi) = 1` of the row-stochastic
+/// matrix `p`, by Gaussian elimination with partial pivoting. `None` if it is not unique.
+pub fn steady_state(p: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let n = p.len();
+    if n == 0 { return None; }
+    // (p^T - I) pi = 0, with the last equation replaced by sum(pi) = 1.
+    let mut a: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            let mut row: Vec<f64> = (0..n).map(|j| p[j][i] - if i == j { 1.0 } else { 0.0 }).collect();
+            row.push(0.0);
+            row
+        })
+        .collect();
+    a[n - 1] = vec![1.0; n + 1];
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 { return None; }
+        a.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for (row, values) in a.iter_mut().enumerate() {
+            if row == col { continue; }
+            let factor = values[col] / pivot_row[col];
+            for (x, &y) in values[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * y;
+            }
+        }
+    }
+    Some((0..n).map(|i| a[i][n] / a[i][i]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_two_state_chain() {
+        let p = vec![vec![0.9, 0.1], vec![0.5, 0.5]];
+        let pi = steady_state(&p).unwrap();
+        assert_close(&pi, &[5.0 / 6.0, 1.0 / 6.0]);
+
+        for summation in [Summation::Naive, Summation::Kahan] {
+            let power = mat_pow(&p, 1_000_000_000, summation, true);
+            assert_close(&power[0], &pi);
+            assert_close(&power[1], &pi);
+        }
+        assert_close(&mat_pow(&p, 2, Summation::Naive, false)[0], &[0.86, 0.14]);
+    }
+
+    #[test]
+    fn test_kahan_dot() {
+        let terms = std::iter::once(1.0).chain(std::iter::repeat(1e-16).take(10_000));
+        assert_eq!(dot(terms.clone(), Summation::Naive), 1.0);
+        assert!((dot(terms, Summation::Kahan) - (1.0 + 1e-12)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_reducible_chain_has_no_unique_steady_state() {
+        assert!(steady_state(&identity(2)).is_none());
+    }
+}
\ No newline at end of file
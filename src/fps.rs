@@ -0,0 +1,230 @@
+use crate::modint::Mod;
+use crate::ntt::convolution;
+
+/// Formal power series over an NTT-friendly prime, `coeffs[i]` being the coefficient of `x^i`.
+/// Methods taking `n` return the first `n` coefficients of the result.
+#[derive(Clone, Default)]
+pub struct Fps<const P: usize> {
+    pub coeffs: Vec<Mod<P>>,
+}
+
+impl<const P: usize> Fps<P> {
+    pub fn new(coeffs: Vec<Mod<P>>) -> Self {
+        Self { coeffs }
+    }
+
+    pub fn len(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Coefficient of `x^i`, zero past the stored ones.
+    pub fn coeff(&self, i: usize) -> Mod<P> {
+        self.coeffs.get(i).copied().unwrap_or_default()
+    }
+
+    /// The first `n` coefficients, zero-padded.
+    pub fn truncated(&self, n: usize) -> Self {
+        let mut coeffs = self.coeffs[..n.min(self.len())].to_vec();
+        coeffs.resize(n, Mod::new(0));
+        Self { coeffs }
+    }
+
+    pub fn derivative(&self) -> Self {
+        let coeffs = self.coeffs.iter().enumerate().skip(1).map(|(i, &c)| c * Mod::new(i)).collect();
+        Self { coeffs }
+    }
+
+    /// Antiderivative with zero constant term.
+    pub fn integral(&self) -> Self {
+        let indices: Vec<Mod<P>> = (1..=self.len()).map(Mod::new).collect();
+        let mut coeffs = vec![Mod::new(0)];
+        coeffs.extend(self.coeffs.iter().zip(Mod::batch_inverse(&indices)).map(|(&c, inv)| c * inv));
+        Self { coeffs }
+    }
+
+    /// `1 / self`; the constant term must be nonzero.
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(self.coeff(0).value != 0, "constant term must be invertible");
+        let mut g = Self::new(vec![self.coeff(0).inv()]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            // g <- g (2 - f g)
+            let mut t = (&self.truncated(m) * &g).truncated(m);
+            for c in t.coeffs.iter_mut() {
+                *c = -*c;
+            }
+            t.coeffs[0] += Mod::new(2);
+            g = (&g * &t).truncated(m);
+        }
+        g.truncated(n)
+    }
+
+    /// The constant term must be one.
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.coeff(0).value == 1, "log needs constant term 1");
+        if n == 0 { return Self::default(); }
+        let quotient = &self.truncated(n).derivative() * &self.inv(n);
+        quotient.truncated(n - 1).integral()
+    }
+
+    /// The constant term must be zero.
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.coeff(0).value == 0, "exp needs constant term 0");
+        let mut g = Self::new(vec![Mod::new(1)]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            // g <- g (1 + f - log g)
+            let mut t = &self.truncated(m) - &g.log(m);
+            t.coeffs[0] += Mod::new(1);
+            g = (&g * &t).truncated(m);
+        }
+        g.truncated(n)
+    }
+
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            return Self::new(vec![Mod::new(1)]).truncated(n);
+        }
+        let zero = Self::default().truncated(n);
+        let Some(zeros) = self.coeffs.iter().position(|c| c.value != 0) else { return zero };
+        let shift = match zeros.checked_mul(k as usize) {
+            Some(shift) if shift < n => shift,
+            _ => return zero,
+        };
+        let lead = self.coeffs[zeros];
+        let lead_inv = lead.inv();
+        let rest = Self::new(self.coeffs[zeros..].iter().map(|&c| c * lead_inv).collect());
+        let mut log = rest.log(n - shift);
+        for c in log.coeffs.iter_mut() {
+            *c *= Mod::from(k);
+        }
+        let scale = lead.pow((k % (P as u64 - 1)) as usize);
+        let mut coeffs = vec![Mod::new(0); shift];
+        coeffs.extend(log.exp(n - shift).coeffs.into_iter().map(|c| c * scale));
+        Self { coeffs }
+    }
+
+    /// Polynomial long division; `rhs` must have a nonzero leading coefficient.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let degree = rhs.coeffs.iter().rposition(|c| c.value != 0).expect("division by zero polynomial");
+        let divisor = rhs.truncated(degree + 1);
+        if self.len() <= degree {
+            return (Self::default(), self.clone());
+        }
+        let quotient_len = self.len() - degree;
+        let reversed = |f: &Self| Self::new(f.coeffs.iter().rev().copied().collect());
+        let mut quotient = (&reversed(self).truncated(quotient_len) * &reversed(&divisor).inv(quotient_len))
+            .truncated(quotient_len);
+        quotient.coeffs.reverse();
+        let remainder = (self - &(&divisor * &quotient)).truncated(degree);
+        (quotient, remainder)
+    }
+}
+
+impl<const P: usize> std::ops::Add for &Fps<P> {
+    type Output = Fps<P>;
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self.truncated(self.len().max(rhs.len()));
+        for (r, &c) in result.coeffs.iter_mut().zip(&rhs.coeffs) {
+            *r += c;
+        }
+        result
+    }
+}
+
+impl<const P: usize> std::ops::Sub for &Fps<P> {
+    type Output = Fps<P>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self.truncated(self.len().max(rhs.len()));
+        for (r, &c) in result.coeffs.iter_mut().zip(&rhs.coeffs) {
+            *r -= c;
+        }
+        result
+    }
+}
+
+impl<const P: usize> std::ops::Mul for &Fps<P> {
+    type Output = Fps<P>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Fps::new(convolution(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    const P: usize = 998_244_353;
+
+    fn values(f: &Fps<P>) -> Vec<usize> {
+        f.coeffs.iter().map(|c| c.value).collect()
+    }
+
+    fn random_fps(rng: &mut XorShift64, n: usize, constant: usize) -> Fps<P> {
+        let mut coeffs: Vec<Mod<P>> = (0..n).map(|_| Mod::new(rng.gen_range(0..P))).collect();
+        coeffs[0] = Mod::new(constant);
+        Fps::new(coeffs)
+    }
+
+    #[test]
+    fn test_inv_log_exp() {
+        let mut rng = XorShift64::new(17);
+        for n in [1, 2, 7, 100] {
+            let f = random_fps(&mut rng, n, 5);
+            let mut one = vec![0; n];
+            one[0] = 1;
+            assert_eq!(values(&(&f * &f.inv(n)).truncated(n)), one);
+
+            let g = random_fps(&mut rng, n, 1);
+            assert_eq!(values(&g.log(n).exp(n)), values(&g));
+        }
+    }
+
+    #[test]
+    fn test_partition_numbers() {
+        // log of prod 1 / (1 - x^k) is sum over m of sigma(m) / m * x^m
+        let n = 12;
+        let log = Fps::<P>::new((0..n).map(|m| {
+            if m == 0 { return Mod::new(0); }
+            let sigma: usize = (1..=m).filter(|d| m % d == 0).sum();
+            Mod::new(sigma) / Mod::new(m)
+        }).collect());
+        assert_eq!(values(&log.exp(n)), [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42, 56]);
+    }
+
+    #[test]
+    fn test_pow() {
+        let mut rng = XorShift64::new(18);
+        let f = Fps::<P>::new(vec![Mod::new(0), Mod::new(0), Mod::new(3), Mod::new(1), Mod::new(4)]);
+        let n = 20;
+        let mut expected = Fps::new(vec![Mod::new(1)]);
+        for k in 0..12 {
+            assert_eq!(values(&f.pow(k, n)), values(&expected.truncated(n)));
+            expected = &expected * &f;
+        }
+        let g = random_fps(&mut rng, 30, 7);
+        assert_eq!(values(&g.pow(3, 30)), values(&(&(&g * &g) * &g).truncated(30)));
+        assert_eq!(values(&Fps::<P>::default().pow(0, 3)), [1, 0, 0]);
+        assert_eq!(values(&f.pow(u64::MAX, 5)), [0; 5]);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = XorShift64::new(19);
+        for (n, m) in [(10, 3), (3, 10), (50, 50), (100, 40)] {
+            let a = random_fps(&mut rng, n, 1);
+            let b = random_fps(&mut rng, m, 2);
+            let (q, r) = a.div_rem(&b);
+            assert!(r.len() < m);
+            let back = (&(&b * &q) + &r).truncated(n);
+            assert_eq!(values(&back), values(&a));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,140 @@
+use std::hash::Hash;
+
+use crate::fast_hash::FastHashMap;
+
+/// Result for the player to move.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl Outcome {
+    /// The same result seen by the opponent.
+    pub fn flip(self) -> Self {
+        match self {
+            Outcome::Win => Outcome::Lose,
+            Outcome::Lose => Outcome::Win,
+            Outcome::Draw => Outcome::Draw,
+        }
+    }
+}
+
+/// A two-player game whose position graph is acyclic.
+pub trait Game {
+    type State: Hash + Eq + Clone;
+    /// `Some` for positions decided by the rules; otherwise the game continues.
+    fn terminal(&self, state: &Self::State) -> Option<Outcome>;
+    /// Positions reachable in one move. A non-terminal position without moves is lost.
+    fn moves(&self, state: &Self::State) -> Vec<Self::State>;
+}
+
+/// Memoized minimax over `Game` positions. The search stops at the first winning move,
+/// which is alpha-beta pruning for three-valued outcomes.
+pub struct GameSolver<G> where
+    G: Game {
+    game: G,
+    memo: FastHashMap<G::State, Outcome>,
+}
+
+impl<G> GameSolver<G> where
+    G: Game {
+    pub fn new(game: G) -> Self {
+        Self { game, memo: FastHashMap::default() }
+    }
+
+    pub fn solve(&mut self, state: &G::State) -> Outcome {
+        if let Some(&outcome) = self.memo.get(state) { return outcome; }
+        let outcome = match self.game.terminal(state) {
+            Some(outcome) => outcome,
+            None => {
+                let mut best = Outcome::Lose;
+                for next in self.game.moves(state) {
+                    match self.solve(&next).flip() {
+                        Outcome::Win => {
+                            best = Outcome::Win;
+                            break;
+                        }
+                        Outcome::Draw => best = Outcome::Draw,
+                        Outcome::Lose => {}
+                    }
+                }
+                best
+            }
+        };
+        self.memo.insert(state.clone(), outcome);
+        outcome
+    }
+
+    /// A move achieving `solve(state)`, if there is any move.
+    pub fn best_move(&mut self, state: &G::State) -> Option<G::State> {
+        let target = self.solve(state);
+        self.game.moves(state).into_iter().find(|next| self.solve(next).flip() == target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Take 1, 3 or 4 stones; whoever cannot move loses.
+    struct Subtraction;
+
+    impl Game for Subtraction {
+        type State = usize;
+        fn terminal(&self, _: &usize) -> Option<Outcome> { None }
+        fn moves(&self, &n: &usize) -> Vec<usize> {
+            [1, 3, 4].iter().filter(|&&k| k <= n).map(|&k| n - k).collect()
+        }
+    }
+
+    // Tic-tac-toe on a 9-cell board, 0 empty and 1/2 the players.
+    struct TicTacToe;
+
+    impl TicTacToe {
+        fn has_line(board: &[u8; 9]) -> bool {
+            const LINES: [[usize; 3]; 8] = [
+                [0, 1, 2], [3, 4, 5], [6, 7, 8],
+                [0, 3, 6], [1, 4, 7], [2, 5, 8],
+                [0, 4, 8], [2, 4, 6],
+            ];
+            LINES.iter().any(|&[a, b, c]| board[a] != 0 && board[a] == board[b] && board[b] == board[c])
+        }
+    }
+
+    impl Game for TicTacToe {
+        type State = [u8; 9];
+        fn terminal(&self, board: &[u8; 9]) -> Option<Outcome> {
+            // The previous player is the only one who can have just completed a line.
+            if Self::has_line(board) { return Some(Outcome::Lose); }
+            if board.iter().all(|&c| c != 0) { return Some(Outcome::Draw); }
+            None
+        }
+        fn moves(&self, board: &[u8; 9]) -> Vec<[u8; 9]> {
+            let player = if board.iter().filter(|&&c| c != 0).count() % 2 == 0 { 1 } else { 2 };
+            let mut result = Vec::new();
+            for i in (0..9).filter(|&i| board[i] == 0) {
+                let mut next = *board;
+                next[i] = player;
+                result.push(next);
+            }
+            result
+        }
+    }
+
+    #[test]
+    fn test_subtraction_game() {
+        let mut solver = GameSolver::new(Subtraction);
+        let losing: Vec<usize> = (0..20).filter(|n| solver.solve(n) == Outcome::Lose).collect();
+        assert_eq!(losing, [0, 2, 7, 9, 14, 16]);
+        assert_eq!(solver.best_move(&10), Some(9));
+    }
+
+    #[test]
+    fn test_tic_tac_toe_is_a_draw() {
+        let mut solver = GameSolver::new(TicTacToe);
+        assert_eq!(solver.solve(&[0; 9]), Outcome::Draw);
+        assert_eq!(solver.solve(&[1, 1, 0, 2, 2, 0, 0, 0, 0]), Outcome::Win);
+    }
+}
\ No newline at end of file
@@ -1,3 +1,9 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::flat_deque::FlatDeque;
+use crate::monoid::Number;
+
 #[derive(Clone)]
 pub struct DirectedGraph {
     pub n: usize,
@@ -13,19 +19,101 @@ impl DirectedGraph {
         self.adj[u].push(v);
     }
 
+    /// Components in topological order of the condensation, members in increasing order.
+    /// Among the valid orders, the one that always takes the available component with
+    /// the smallest member is returned, so the result depends only on the edge set.
     pub fn find_sccs(&self) -> Vec<Vec<usize>> {
         let postorder = self.postorder();
         let transposed = self.transposed();
-        let mut result = Vec::new();
+        let mut sccs = Vec::new();
         let mut visited = vec![false; self.n];
         for start in postorder.into_iter().rev() {
             if !visited[start] {
                 let mut single_scc = Vec::new();
                 transposed.dfs(start, &mut visited, &mut single_scc);
-                result.push(single_scc);
+                single_scc.sort_unstable();
+                sccs.push(single_scc);
             }
         }
-        result
+
+        let mut component = vec![0; self.n];
+        for (c, scc) in sccs.iter().enumerate() {
+            for &v in scc {
+                component[v] = c;
+            }
+        }
+        let mut in_degrees = vec![0; sccs.len()];
+        for v in 0..self.n {
+            for &u in &self.adj[v] {
+                if component[u] != component[v] {
+                    in_degrees[component[u]] += 1;
+                }
+            }
+        }
+        // Kahn's algorithm keyed by the smallest member.
+        let mut heap: BinaryHeap<_> = (0..sccs.len())
+            .filter(|&c| in_degrees[c] == 0)
+            .map(|c| Reverse((sccs[c][0], c)))
+            .collect();
+        let mut order = Vec::with_capacity(sccs.len());
+        while let Some(Reverse((_, c))) = heap.pop() {
+            order.push(c);
+            for &v in &sccs[c] {
+                for &u in &self.adj[v] {
+                    let d = component[u];
+                    if d == c { continue; }
+                    in_degrees[d] -= 1;
+                    if in_degrees[d] == 0 {
+                        heap.push(Reverse((sccs[d][0], d)));
+                    }
+                }
+            }
+        }
+        let mut sccs: Vec<Option<Vec<usize>>> = sccs.into_iter().map(Some).collect();
+        order.into_iter().map(|c| sccs[c].take().unwrap()).collect()
+    }
+
+    /// Maximum total weight of the vertices on a walk, where a strongly connected
+    /// component counts the weights of all its members once.
+    pub fn longest_weighted_path_in_dag_of_sccs(&self, weights: &[i64]) -> i64 {
+        let sccs = self.find_sccs();
+        let mut component = vec![0; self.n];
+        for (c, scc) in sccs.iter().enumerate() {
+            for &v in scc {
+                component[v] = c;
+            }
+        }
+        let mut best = vec![0; sccs.len()]; // best walk starting in component c
+        for c in (0..sccs.len()).rev() {
+            let mut next_best = 0;
+            for &v in &sccs[c] {
+                for &u in &self.adj[v] {
+                    if component[u] != c {
+                        next_best = next_best.max(best[component[u]]);
+                    }
+                }
+            }
+            best[c] = sccs[c].iter().map(|&v| weights[v]).sum::<i64>() + next_best;
+        }
+        best.into_iter().max().unwrap_or(0)
+    }
+
+    /// Unweighted distances from `src`, `None` for unreachable vertices.
+    pub fn bfs(&self, src: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.n];
+        let mut queue = FlatDeque::with_capacity(self.n);
+        dist[src] = Some(0);
+        queue.push_back(src);
+        while let Some(u) = queue.pop_front() {
+            let d = dist[u].unwrap();
+            for &v in &self.adj[u] {
+                if dist[v].is_none() {
+                    dist[v] = Some(d + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+        dist
     }
 
     pub fn postorder(&self) -> Vec<usize> {
@@ -57,4 +145,196 @@ impl DirectedGraph {
         }
         result
     }
+}
+
+/// Directed graph with a weight on every edge.
+#[derive(Clone)]
+pub struct WeightedGraph<W> {
+    pub n: usize,
+    pub adj: Vec<Vec<(usize, W)>>,
+}
+
+impl<W> WeightedGraph<W> where
+    W: Number {
+    pub fn new(n: usize) -> Self {
+        Self { n, adj: vec![Vec::new(); n] }
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: W) {
+        self.adj[u].push((v, weight));
+    }
+
+    /// Shortest distances from `src`, `None` for unreachable vertices. Weights must be
+    /// non-negative. O((n + m) log m).
+    pub fn dijkstra(&self, src: usize) -> Vec<Option<W>> {
+        self.dijkstra_with_parents(src).0
+    }
+
+    /// Also returns each vertex's predecessor on a shortest path, for `restore_path`.
+    pub fn dijkstra_with_parents(&self, src: usize) -> (Vec<Option<W>>, Vec<Option<usize>>) {
+        let mut dist = vec![None; self.n];
+        let mut parents = vec![None; self.n];
+        let mut heap = BinaryHeap::new();
+        dist[src] = Some(W::ZERO);
+        heap.push(Reverse((W::ZERO, src)));
+        while let Some(Reverse((d, u))) = heap.pop() {
+            if dist[u] != Some(d) { continue; }
+            for &(v, weight) in &self.adj[u] {
+                let candidate = d + weight;
+                if dist[v].map_or(true, |current| candidate < current) {
+                    dist[v] = Some(candidate);
+                    parents[v] = Some(u);
+                    heap.push(Reverse((candidate, v)));
+                }
+            }
+        }
+        (dist, parents)
+    }
+}
+
+impl WeightedGraph<usize> {
+    /// Shortest distances for weights in `{0, 1}`, in O(n + m) with a deque.
+    pub fn zero_one_bfs(&self, src: usize) -> Vec<Option<usize>> {
+        let edges: usize = self.adj.iter().map(Vec::len).sum();
+        let mut dist = vec![None; self.n];
+        let mut done = vec![false; self.n];
+        // each edge pushes at most once, since every vertex is expanded once
+        let mut deque = FlatDeque::with_capacity(edges + 1);
+        dist[src] = Some(0);
+        deque.push_back(src);
+        while let Some(u) = deque.pop_front() {
+            if std::mem::replace(&mut done[u], true) { continue; }
+            let d = dist[u].unwrap();
+            for &(v, weight) in &self.adj[u] {
+                assert!(weight <= 1, "zero_one_bfs needs weights 0 or 1");
+                if dist[v].map_or(true, |current| d + weight < current) {
+                    dist[v] = Some(d + weight);
+                    if weight == 0 {
+                        deque.push_front(v);
+                    } else {
+                        deque.push_back(v);
+                    }
+                }
+            }
+        }
+        dist
+    }
+}
+
+/// Vertices of the path ending at `target` that `parents` describes, starting from the
+/// vertex without a parent. `target` must have been reached.
+pub fn restore_path(parents: &[Option<usize>], target: usize) -> Vec<usize> {
+    let mut path = vec![target];
+    while let Some(parent) = parents[*path.last().unwrap()] {
+        path.push(parent);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_find_sccs_order() {
+        let mut graph = DirectedGraph::new(6);
+        // {3, 1, 4} -> {0, 5} -> {2}
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 4);
+        graph.add_edge(4, 3);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 0);
+        graph.add_edge(0, 5);
+        graph.add_edge(0, 2);
+
+        let expected = vec![vec![1, 3, 4], vec![0, 5], vec![2]];
+        assert_eq!(graph.find_sccs(), expected);
+        // Independent of the order edges were added in.
+        let mut reversed = DirectedGraph::new(6);
+        for v in (0..6).rev() {
+            for &u in graph.adj[v].iter().rev() {
+                reversed.add_edge(v, u);
+            }
+        }
+        assert_eq!(reversed.find_sccs(), expected);
+    }
+
+    #[test]
+    fn test_find_sccs_order_with_incomparable_components() {
+        // {4, 5} -> {1}, {0} -> {3}, {2} on its own: ties go to the smallest member.
+        let edges = [(4, 5), (5, 4), (5, 1), (0, 3), (4, 1)];
+        let expected = vec![vec![0], vec![2], vec![3], vec![4, 5], vec![1]];
+        let mut rng = XorShift64::new(8);
+        let mut order: Vec<usize> = (0..edges.len()).collect();
+        for _ in 0..50 {
+            for i in (1..order.len()).rev() {
+                order.swap(i, rng.gen_range(0..i + 1));
+            }
+            let mut graph = DirectedGraph::new(6);
+            for &i in &order {
+                graph.add_edge(edges[i].0, edges[i].1);
+            }
+            assert_eq!(graph.find_sccs(), expected);
+        }
+    }
+
+    #[test]
+    fn test_longest_weighted_path_in_dag_of_sccs() {
+        let mut graph = DirectedGraph::new(6);
+        // {0, 1} -> 2 -> {3, 4}, {0, 1} -> 5
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 0);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 3);
+        graph.add_edge(0, 5);
+
+        let weights = [1, 2, -4, 3, 5, 6];
+        // {0, 1} -> 5 gives 9, beating {0, 1} -> 2 -> {3, 4} with 7.
+        assert_eq!(graph.longest_weighted_path_in_dag_of_sccs(&weights), 9);
+        let weights = [1, 2, -1, 3, 5, 6];
+        assert_eq!(graph.longest_weighted_path_in_dag_of_sccs(&weights), 10);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let mut graph = WeightedGraph::new(5);
+        graph.add_edge(0, 1, 4u64);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 1, 2);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(2, 3, 5);
+        graph.add_edge(3, 0, 0);
+
+        assert_eq!(graph.dijkstra(0), [Some(0), Some(3), Some(1), Some(4), None]);
+        let (dist, parents) = graph.dijkstra_with_parents(2);
+        assert_eq!(dist, [Some(3), Some(2), Some(0), Some(3), None]);
+        assert_eq!(restore_path(&parents, 0), [2, 1, 3, 0]);
+        assert_eq!(restore_path(&parents, 2), [2]);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let mut graph = DirectedGraph::new(5);
+        for (u, v) in [(0, 1), (1, 2), (0, 2), (2, 3), (4, 0)] {
+            graph.add_edge(u, v);
+        }
+        assert_eq!(graph.bfs(0), [Some(0), Some(1), Some(1), Some(2), None]);
+    }
+
+    #[test]
+    fn test_zero_one_bfs_against_dijkstra() {
+        let mut rng = crate::rng::XorShift64::new(5);
+        for _ in 0..50 {
+            let n = 30;
+            let mut graph = WeightedGraph::new(n);
+            for _ in 0..80 {
+                graph.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..2));
+            }
+            assert_eq!(graph.zero_one_bfs(0), graph.dijkstra(0));
+        }
+    }
 }
\ No newline at end of file
@@ -1,4 +1,8 @@
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+use crate::flat_deque::FlatDeque;
+
+pub const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Position {
     pos: (usize, usize),
 }
@@ -23,26 +27,241 @@ impl Position {
                 return Some(Self { pos });
             }
         }
-        return None;
+        None
+    }
+
+    /// Row-major index in a grid of the given width.
+    pub fn to_index(self, width: usize) -> usize {
+        debug_assert!(self.pos.1 < width, "column {} out of range for width {}", self.pos.1, width);
+        self.pos.0 * width + self.pos.1
     }
 
-    pub fn char_at(self, grid: &Vec<Vec<char>>) -> char {
+    pub fn from_index(index: usize, width: usize) -> Self {
+        Self { pos: (index / width, index % width) }
+    }
+
+    pub fn char_at(self, grid: &[Vec<char>]) -> char {
         grid[self.pos.0][self.pos.1]
     }
 
-    pub fn is_space_in(self, grid: &Vec<Vec<char>>) -> bool {
+    pub fn is_space_in(self, grid: &[Vec<char>]) -> bool {
         self.char_at(grid) == '.'
     }
 
-    pub fn is_wall_in(self, grid: &Vec<Vec<char>>) -> bool {
+    pub fn is_wall_in(self, grid: &[Vec<char>]) -> bool {
         self.char_at(grid) == '#'
     }
 
-    pub fn is_already(self, visited: &Vec<Vec<bool>>) -> bool {
+    pub fn is_already(self, visited: &[Vec<bool>]) -> bool {
         visited[self.pos.0][self.pos.1]
     }
 
-    pub fn mark_as(self, visited: &mut Vec<Vec<bool>>) {
+    pub fn mark_as(self, visited: &mut [Vec<bool>]) {
         visited[self.pos.0][self.pos.1] = true;
     }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> where
+    T: Clone {
+    pub fn new(size: (usize, usize), value: T) -> Self {
+        let (height, width) = size;
+        let cells = vec![value; height * width];
+        Self { height, width, cells }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height * self.width).map(move |i| Position::from_index(i, width))
+    }
+
+    fn cell_index(&self, position: Position) -> usize {
+        let (i, j) = position.pos;
+        assert!(i < self.height && j < self.width, "({}, {}) out of range for {}x{}", i, j, self.height, self.width);
+        position.to_index(self.width)
+    }
+}
+
+impl<T> std::ops::Index<Position> for Grid<T> {
+    type Output = T;
+    fn index(&self, position: Position) -> &Self::Output {
+        &self.cells[self.cell_index(position)]
+    }
+}
+
+impl<T> std::ops::IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let index = self.cell_index(position);
+        &mut self.cells[index]
+    }
+}
+
+/// Collects rows; every row must have the same length.
+impl<T> FromIterator<Vec<T>> for Grid<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Self {
+        let mut height = 0;
+        let mut width = 0;
+        let mut cells = Vec::new();
+        for row in iter {
+            if height == 0 {
+                width = row.len();
+            }
+            assert_eq!(row.len(), width, "grid rows must have the same length");
+            cells.extend(row);
+            height += 1;
+        }
+        Self { height, width, cells }
+    }
+}
+
+/// For each cell, the distance to the nearest start and that start's index in `starts`,
+/// moving in four directions through non-`'#'` cells.
+pub fn multi_source_bfs(starts: &[Position], grid: &Grid<char>) -> Grid<Option<(usize, usize)>> {
+    let size = grid.size();
+    let mut result = Grid::new(size, None);
+    let mut queue = FlatDeque::with_capacity(size.0 * size.1);
+    for (source, &start) in starts.iter().enumerate() {
+        let index = start.to_index(size.1);
+        if grid.cells[index] != '#' && result.cells[index].is_none() {
+            result.cells[index] = Some((0, source));
+            queue.push_back(index);
+        }
+    }
+    while let Some(current) = queue.pop_front() {
+        let (dist, source) = result.cells[current].unwrap();
+        for (_, next) in flat_neighbors(current, size) {
+            if grid.cells[next] != '#' && result.cells[next].is_none() {
+                result.cells[next] = Some((dist + 1, source));
+                queue.push_back(next);
+            }
+        }
+    }
+    result
+}
+
+/// `(direction, index)` of the in-bounds neighbors of the cell at flat `index`.
+fn flat_neighbors(index: usize, size: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+    let position = Position::from_index(index, size.1);
+    DIRECTIONS.iter().enumerate().filter_map(move |(direction, &delta)| {
+        position.go(delta, size).map(|next| (direction, next.to_index(size.1)))
+    })
+}
+
+/// BFS over (cell, state) pairs for a small automaton riding on the grid, e.g. collected
+/// keys or facing direction. `transition(state, direction, next_cell)` gives the state after
+/// moving in `DIRECTIONS[direction]` onto `next_cell`, or `None` if the move is forbidden.
+/// Returns `dist[state][position]`. O(HW * states).
+pub fn product_bfs<T>(
+    grid: &Grid<T>,
+    states: usize,
+    start: (Position, usize),
+    transition: impl Fn(usize, usize, &T) -> Option<usize>,
+) -> Vec<Grid<Option<usize>>> {
+    let size = grid.size();
+    let mut dist = vec![Grid::new(size, None); states];
+    let cells = size.0 * size.1;
+    // queue entries are state * cells + flat index
+    let mut queue = FlatDeque::with_capacity(states * cells);
+    let start_index = start.0.to_index(size.1);
+    dist[start.1].cells[start_index] = Some(0);
+    queue.push_back(start.1 * cells + start_index);
+    while let Some(entry) = queue.pop_front() {
+        let (state, current) = (entry / cells, entry % cells);
+        let d = dist[state].cells[current].unwrap();
+        for (direction, next) in flat_neighbors(current, size) {
+            let Some(next_state) = transition(state, direction, &grid.cells[next]) else { continue };
+            if dist[next_state].cells[next].is_none() {
+                dist[next_state].cells[next] = Some(d + 1);
+                queue.push_back(next_state * cells + next);
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multi_source_bfs() {
+        let grid: Grid<char> = ["..#.", "..#.", "...."]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let starts = [Position::new((0, 0)), Position::new((0, 3))];
+        let result = multi_source_bfs(&starts, &grid);
+
+        assert_eq!(result[Position::new((0, 1))], Some((1, 0)));
+        assert_eq!(result[Position::new((0, 2))], None);
+        assert_eq!(result[Position::new((2, 3))], Some((2, 1)));
+        // The wall column forces the right start around the bottom row.
+        assert_eq!(result[Position::new((2, 2))], Some((3, 1)));
+        assert_eq!(result[Position::new((2, 1))], Some((3, 0)));
+    }
+
+    #[test]
+    fn test_position_index_roundtrip() {
+        let grid = Grid::new((3, 5), 0);
+        for (i, position) in grid.positions().enumerate() {
+            assert_eq!(position.to_index(5), i);
+            assert!(Position::from_index(i, 5) == position);
+        }
+        assert_eq!(Position::new((2, 1)).to_index(5), 11);
+    }
+
+    #[test]
+    fn test_product_bfs_with_keys() {
+        // Door 'A' opens with key 'a'; the state is the set of collected keys.
+        let grid: Grid<char> = ["S.A.G", ".####", "a...."]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let dist = product_bfs(&grid, 2, (Position::new((0, 0)), 0), |keys, _, &cell| match cell {
+            '#' => None,
+            'a' => Some(1),
+            'A' if keys == 0 => None,
+            _ => Some(keys),
+        });
+        let goal = Position::new((0, 4));
+        assert_eq!(dist[0][goal], None);
+        // Down to the key, back to the start, then through the door.
+        assert_eq!(dist[1][goal], Some(8));
+        assert_eq!(dist[0][Position::new((2, 4))], None);
+        assert_eq!(dist[1][Position::new((2, 4))], Some(6));
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 5) out of range for 3x3")]
+    fn test_index_column_out_of_range_panics() {
+        let grid = Grid::new((3, 3), 0);
+        let _ = grid[Position::new((0, 5))];
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "column 3 out of range for width 3")]
+    fn test_to_index_column_out_of_range_panics() {
+        Position::new((1, 3)).to_index(3);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::ops::RangeBounds;
+
+use crate::fenwick::FenwickCompatible;
+use crate::range::to_half_open;
+
+/// Difference array for offline range additions: O(1) per `add`, O(n) to `build`.
+/// Use a `FenwickTree` instead when queries interleave with updates.
+#[derive(Clone)]
+pub struct Imos<T> where
+    T: FenwickCompatible {
+    diffs: Vec<T>, // one past the end so `end == len` needs no check
+}
+
+impl<T> Imos<T> where
+    T: FenwickCompatible {
+    pub fn new(size: usize) -> Self {
+        Self { diffs: vec![T::zero(); size + 1] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.diffs.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn add(&mut self, range: impl RangeBounds<usize>, val: T) {
+        let (begin, end) = to_half_open(range, self.len());
+        if begin >= end { return; }
+        self.diffs[begin].add_assign(val);
+        self.diffs[end].add_assign(val.neg());
+    }
+
+    /// Point values after all additions.
+    pub fn build(&self) -> Vec<T> {
+        let mut acc = T::zero();
+        self.diffs[..self.len()].iter().map(|&d| {
+            acc.add_assign(d);
+            acc
+        }).collect()
+    }
+
+    /// `result[i]` is the sum of the first `i` point values, so range sums are differences.
+    pub fn build_prefix(&self) -> Vec<T> {
+        let mut acc = T::zero();
+        let mut result = vec![acc];
+        for value in self.build() {
+            acc.add_assign(value);
+            result.push(acc);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imos() {
+        let mut imos = Imos::<isize>::new(6);
+        imos.add(1..4, 3);
+        imos.add(2.., -1);
+        imos.add(..=0, 5);
+        imos.add(3..3, 100);
+        assert_eq!(imos.build(), [5, 3, 2, 2, -1, -1]);
+        assert_eq!(imos.build_prefix(), [0, 5, 8, 10, 12, 11, 10]);
+        assert!(Imos::<isize>::new(0).build().is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+/// Sorts half-open intervals `[l, r)` and merges the ones that overlap or touch, in place.
+pub fn merge_intervals(intervals: &mut Vec<(i64, i64)>) {
+    intervals.sort_unstable();
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+    for &(l, r) in intervals.iter() {
+        match merged.last_mut() {
+            Some(last) if l <= last.1 => last.1 = last.1.max(r),
+            _ => merged.push((l, r)),
+        }
+    }
+    *intervals = merged;
+}
+
+/// Indices of a maximum set of pairwise disjoint half-open intervals, chosen greedily
+/// by right endpoint and returned in that order.
+pub fn max_non_overlapping(intervals: &[(i64, i64)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_unstable_by_key(|&i| intervals[i].1);
+    let mut result = Vec::new();
+    let mut free_from = i64::MIN;
+    for i in order {
+        let (l, r) = intervals[i];
+        if l >= free_from {
+            result.push(i);
+            free_from = r;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_intervals() {
+        let mut intervals = vec![(5, 8), (1, 3), (2, 4), (8, 9), (11, 12), (0, 1)];
+        merge_intervals(&mut intervals);
+        assert_eq!(intervals, [(0, 4), (5, 9), (11, 12)]);
+    }
+
+    #[test]
+    fn test_max_non_overlapping() {
+        let intervals = [(1, 4), (3, 5), (0, 6), (5, 7), (3, 9), (5, 9), (6, 10), (8, 11)];
+        assert_eq!(max_non_overlapping(&intervals), [0, 3, 7]);
+        assert!(max_non_overlapping(&[]).is_empty());
+    }
+}
\ No newline at end of file
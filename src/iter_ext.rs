@@ -0,0 +1,100 @@
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+
+use crate::fast_hash::FastHashMap;
+
+/// Small itertools-like adapters for any iterator.
+pub trait ProconIterExt: Iterator + Sized {
+    /// Consecutive chunks of exactly `size` items; a shorter tail is dropped.
+    fn chunks_exact_vec(self, size: usize) -> Vec<Vec<Self::Item>> {
+        assert!(size > 0, "chunk size must be positive");
+        let mut result = Vec::new();
+        let mut chunk = Vec::with_capacity(size);
+        for item in self {
+            chunk.push(item);
+            if chunk.len() == size {
+                result.push(std::mem::replace(&mut chunk, Vec::with_capacity(size)));
+            }
+        }
+        result
+    }
+
+    /// All contiguous windows of `size` items.
+    fn windows_vec(self, size: usize) -> Vec<Vec<Self::Item>> where
+        Self::Item: Clone {
+        assert!(size > 0, "window size must be positive");
+        let items: Vec<_> = self.collect();
+        items.windows(size).map(|window| window.to_vec()).collect()
+    }
+
+    /// Index of the first maximum.
+    fn position_max(self) -> Option<usize> where
+        Self::Item: Ord {
+        let mut best: Option<(usize, Self::Item)> = None;
+        for (i, item) in self.enumerate() {
+            if best.as_ref().map_or(true, |(_, max)| item > *max) {
+                best = Some((i, item));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Index of the first minimum.
+    fn position_min(self) -> Option<usize> where
+        Self::Item: Ord {
+        let mut best: Option<(usize, Self::Item)> = None;
+        for (i, item) in self.enumerate() {
+            if best.as_ref().map_or(true, |(_, min)| item < *min) {
+                best = Some((i, item));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    fn counts(self) -> FastHashMap<Self::Item, usize> where
+        Self::Item: Hash + Eq {
+        let mut result = FastHashMap::default();
+        for item in self {
+            *result.entry(item).or_insert(0) += 1;
+        }
+        result
+    }
+
+    fn join_sep(self, sep: &str) -> String where
+        Self::Item: Display {
+        let mut result = String::new();
+        for (i, item) in self.enumerate() {
+            if i > 0 {
+                result.push_str(sep);
+            }
+            write!(result, "{}", item).unwrap();
+        }
+        result
+    }
+}
+
+impl<I> ProconIterExt for I where
+    I: Iterator {}
+
+#[cfg(test)]
+mod tests {
+    use super::ProconIterExt;
+
+    #[test]
+    fn test_adapters() {
+        let a = [3, 1, 4, 1, 5, 9, 2, 6, 5];
+        assert_eq!(a.iter().chunks_exact_vec(4), [[&3, &1, &4, &1], [&5, &9, &2, &6]]);
+        assert_eq!((1..=4).windows_vec(3), [[1, 2, 3], [2, 3, 4]]);
+        assert_eq!(a.iter().position_max(), Some(5));
+        assert_eq!(a.iter().position_min(), Some(1));
+        assert_eq!(std::iter::empty::<i32>().position_max(), None);
+
+        let counts = a.iter().counts();
+        assert_eq!(counts[&1], 2);
+        assert_eq!(counts[&5], 2);
+        assert_eq!(counts.len(), 7);
+
+        assert_eq!(a[..4].iter().join_sep(" "), "3 1 4 1");
+        assert_eq!(std::iter::empty::<i32>().join_sep(","), "");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,271 @@
+use std::ops::RangeBounds;
+
+use crate::range::to_half_open;
+
+/// Values of type `S` under `combine`, acted on by lazy maps of type `F`.
+/// Implemented on a marker type, usually through `impl_segment_tree_compatible!`.
+pub trait LazySegmentTreeCompatible {
+    type S: Copy;
+    type F: Copy;
+    /// combine(ident(), x) == x && combine(x, ident()) == x
+    fn ident() -> Self::S;
+    fn combine(lhs: Self::S, rhs: Self::S) -> Self::S;
+    /// apply(map_ident(), x) == x
+    fn map_ident() -> Self::F;
+    /// apply(f, combine(x, y)) == combine(apply(f, x), apply(f, y))
+    fn apply(map: Self::F, value: Self::S) -> Self::S;
+    /// apply(compose(f, g), x) == apply(g, apply(f, x))
+    fn compose(first: Self::F, second: Self::F) -> Self::F;
+}
+
+/// Declares a marker type implementing `LazySegmentTreeCompatible` from closures.
+///
+/// ```
+/// // Range add, range (min, index); `None` is the empty range, so every i64 is a valid value.
+/// libprocon_rs::impl_segment_tree_compatible!(
+///     pub MinIndexAdd, S = Option<(i64, usize)>, F = i64,
+///     ident = None,
+///     combine = |a: Option<(i64, usize)>, b: Option<(i64, usize)>| a.zip(b).map(|(x, y)| x.min(y)).or(a).or(b),
+///     map_ident = 0,
+///     apply = |f: i64, x: Option<(i64, usize)>| x.map(|(v, i)| (v + f, i)),
+///     compose = |f: i64, g: i64| f + g,
+/// );
+/// let leaves = [3, 1, 4].into_iter().zip(0..).map(Some);
+/// let mut segtree: libprocon_rs::lazy_segment_tree::LazySegmentTree<MinIndexAdd> = leaves.collect();
+/// segtree.update(1.., 5);
+/// assert_eq!(segtree.query(..), Some((3, 0)));
+/// assert_eq!(segtree.query(1..1), None);
+/// ```
+#[macro_export]
+macro_rules! impl_segment_tree_compatible {
+    (
+        $vis:vis $name:ident, S = $s:ty, F = $f:ty,
+        ident = $ident:expr,
+        combine = $combine:expr,
+        map_ident = $map_ident:expr,
+        apply = $apply:expr,
+        compose = $compose:expr $(,)?
+    ) => {
+        #[derive(Clone, Copy)]
+        $vis struct $name;
+
+        impl $crate::lazy_segment_tree::LazySegmentTreeCompatible for $name {
+            type S = $s;
+            type F = $f;
+            fn ident() -> Self::S { $ident }
+            fn combine(lhs: Self::S, rhs: Self::S) -> Self::S { ($combine)(lhs, rhs) }
+            fn map_ident() -> Self::F { $map_ident }
+            fn apply(map: Self::F, value: Self::S) -> Self::S { ($apply)(map, value) }
+            fn compose(first: Self::F, second: Self::F) -> Self::F { ($compose)(first, second) }
+        }
+    };
+}
+
+pub struct LazySegmentTree<L> where
+    L: LazySegmentTreeCompatible {
+    size: usize,
+    log: u32,
+    values: Vec<L::S>, // 1-indexed, leaves at `1 << log`
+    thunks: Vec<L::F>, // pending for the children of each inner node
+}
+
+impl<L> Clone for LazySegmentTree<L> where
+    L: LazySegmentTreeCompatible {
+    fn clone(&self) -> Self {
+        let values = self.values.clone();
+        let thunks = self.thunks.clone();
+        Self { size: self.size, log: self.log, values, thunks }
+    }
+}
+
+impl<L> LazySegmentTree<L> where
+    L: LazySegmentTreeCompatible {
+    pub fn new(size: usize) -> Self {
+        let log = size.next_power_of_two().trailing_zeros();
+        let values = vec![L::ident(); 2 << log];
+        let thunks = vec![L::map_ident(); 1 << log];
+        Self { size, log, values, thunks }
+    }
+
+    pub fn from_slice(leaves: &[L::S]) -> Self {
+        let mut result = Self::new(leaves.len());
+        let capacity = result.thunks.len();
+        result.values[capacity..capacity + leaves.len()].copy_from_slice(leaves);
+        for k in (1..capacity).rev() {
+            result.recalc(k);
+        }
+        result
+    }
+
+    pub fn update(&mut self, range: impl RangeBounds<usize>, map: L::F) {
+        let (begin, end) = to_half_open(range, self.size);
+        if begin == end { return; }
+        let (begin, end) = (begin + self.thunks.len(), end + self.thunks.len());
+        self.push_boundaries(begin, end);
+        let (mut l, mut r) = (begin, end);
+        while l < r {
+            if l & 1 == 1 {
+                self.apply_node(l, map);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self.apply_node(r, map);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        for i in 1..=self.log {
+            if (begin >> i) << i != begin { self.recalc(begin >> i); }
+            if (end >> i) << i != end { self.recalc((end - 1) >> i); }
+        }
+    }
+
+    pub fn query(&mut self, range: impl RangeBounds<usize>) -> L::S {
+        let (begin, end) = to_half_open(range, self.size);
+        if begin == end { return L::ident(); }
+        let (mut l, mut r) = (begin + self.thunks.len(), end + self.thunks.len());
+        self.push_boundaries(l, r);
+        let mut left = L::ident();
+        let mut right = L::ident();
+        while l < r {
+            if l & 1 == 1 {
+                left = L::combine(left, self.values[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = L::combine(self.values[r], right);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        L::combine(left, right)
+    }
+
+    fn push_boundaries(&mut self, begin: usize, end: usize) {
+        for i in (1..=self.log).rev() {
+            if (begin >> i) << i != begin { self.push(begin >> i); }
+            if (end >> i) << i != end { self.push((end - 1) >> i); }
+        }
+    }
+
+    fn recalc(&mut self, k: usize) {
+        self.values[k] = L::combine(self.values[2 * k], self.values[2 * k + 1]);
+    }
+
+    fn apply_node(&mut self, k: usize, map: L::F) {
+        self.values[k] = L::apply(map, self.values[k]);
+        if k < self.thunks.len() {
+            self.thunks[k] = L::compose(self.thunks[k], map);
+        }
+    }
+
+    fn push(&mut self, k: usize) {
+        let thunk = self.thunks[k];
+        self.apply_node(2 * k, thunk);
+        self.apply_node(2 * k + 1, thunk);
+        self.thunks[k] = L::map_ident();
+    }
+}
+
+impl<L> From<Vec<L::S>> for LazySegmentTree<L> where
+    L: LazySegmentTreeCompatible {
+    fn from(leaves: Vec<L::S>) -> Self {
+        Self::from_slice(&leaves)
+    }
+}
+
+impl<L> FromIterator<L::S> for LazySegmentTree<L> where
+    L: LazySegmentTreeCompatible {
+    fn from_iter<I: IntoIterator<Item = L::S>>(iter: I) -> Self {
+        let leaves: Vec<L::S> = iter.into_iter().collect();
+        Self::from_slice(&leaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modint::Mod;
+    use crate::rng::XorShift64;
+    use crate::segment_tree::Affine;
+
+    impl_segment_tree_compatible!(
+        MinIndexAdd, S = Option<(i64, usize)>, F = i64,
+        ident = None,
+        combine = |a: Option<(i64, usize)>, b: Option<(i64, usize)>| a.zip(b).map(|(x, y)| x.min(y)).or(a).or(b),
+        map_ident = 0,
+        apply = |f: i64, x: Option<(i64, usize)>| x.map(|(v, i)| (v + f, i)),
+        compose = |f: i64, g: i64| f + g,
+    );
+
+    type M = Mod<998_244_353>;
+
+    impl_segment_tree_compatible!(
+        AffineSum, S = (M, M), F = Affine<M>,
+        ident = (M::new(0), M::new(0)),
+        combine = |(s, n): (M, M), (t, m): (M, M)| (s + t, n + m),
+        map_ident = Affine::new(M::new(1), M::new(0)),
+        apply = |f: Affine<M>, (s, n): (M, M)| (f.a * s + f.b * n, n),
+        compose = |f: Affine<M>, g: Affine<M>| f.then(g),
+    );
+
+    #[test]
+    fn test_range_add_range_min_with_index() {
+        let leaves: Vec<_> = [5, 2, 7, 2, 9].iter().enumerate().map(|(i, &x)| Some((x, i))).collect();
+        let mut segtree = LazySegmentTree::<MinIndexAdd>::from(leaves);
+        assert_eq!(segtree.query(..), Some((2, 1)));
+
+        segtree.update(0..2, 4);
+        // values: [9, 6, 7, 2, 9]
+        assert_eq!(segtree.query(..), Some((2, 3)));
+        assert_eq!(segtree.query(..3), Some((6, 1)));
+        segtree.update(2.., -5);
+        // values: [9, 6, 2, -3, 4]
+        assert_eq!(segtree.query(..), Some((-3, 3)));
+        assert_eq!(segtree.query(4..), Some((4, 4)));
+
+        // i64::MAX is an ordinary value, not a marker for empty leaves.
+        let mut segtree: LazySegmentTree<MinIndexAdd> = [Some((i64::MAX, 0)), Some((i64::MAX, 1))].into_iter().collect();
+        segtree.update(1.., -1);
+        assert_eq!(segtree.query(..), Some((i64::MAX - 1, 1)));
+        assert_eq!(segtree.query(..1), Some((i64::MAX, 0)));
+    }
+
+    #[test]
+    fn test_range_affine_range_sum() {
+        let mut segtree: LazySegmentTree<AffineSum> = (1..=5).map(|x| (M::new(x), M::new(1))).collect();
+        assert_eq!(segtree.query(..).0.value, 15);
+
+        segtree.update(1..4, Affine::new(M::new(2), M::new(3)));
+        // values: [1, 7, 9, 11, 5]
+        assert_eq!(segtree.query(..).0.value, 33);
+        segtree.update(..2, Affine::new(M::new(1), M::new(10)));
+        // values: [11, 17, 9, 11, 5]
+        assert_eq!(segtree.query(1..3).0.value, 26);
+        assert_eq!(segtree.query(..).0.value, 53);
+    }
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(3);
+        for size in 1..=20 {
+            let mut naive: Vec<i64> = (0..size).map(|_| rng.gen_range(0..100) as i64).collect();
+            let leaves: Vec<_> = naive.iter().copied().zip(0..).map(Some).collect();
+            let mut segtree = LazySegmentTree::<MinIndexAdd>::from_slice(&leaves);
+            for _ in 0..200 {
+                let begin = rng.gen_range(0..size);
+                let end = rng.gen_range(begin + 1..size + 1);
+                if rng.next_u64() % 2 == 0 {
+                    let add = rng.gen_range(0..100) as i64 - 50;
+                    segtree.update(begin..end, add);
+                    naive[begin..end].iter_mut().for_each(|x| *x += add);
+                } else {
+                    let expected = (begin..end).map(|i| (naive[i], i)).min();
+                    assert_eq!(segtree.query(begin..end), expected);
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
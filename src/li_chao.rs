@@ -0,0 +1,268 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::arena::Arena;
+
+type Line = (i64, i64);
+
+fn eval((k, m): Line, x: i64) -> i64 {
+    k * x + m
+}
+
+/// Index range of `xs` (sorted) covered by the coordinate range `range`.
+fn index_range(xs: &[i64], range: impl RangeBounds<i64>) -> (usize, usize) {
+    let begin = match range.start_bound() {
+        Bound::Included(&l) => xs.partition_point(|&x| x < l),
+        Bound::Excluded(&l) => xs.partition_point(|&x| x <= l),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&r) => xs.partition_point(|&x| x <= r),
+        Bound::Excluded(&r) => xs.partition_point(|&x| x < r),
+        Bound::Unbounded => xs.len(),
+    };
+    (begin, end.max(begin))
+}
+
+fn sorted_coordinates(mut xs: Vec<i64>) -> Vec<i64> {
+    xs.sort_unstable();
+    xs.dedup();
+    xs
+}
+
+/// Li Chao tree over a fixed set of query coordinates, answering max queries.
+/// Lines and segments are added in O(log n) and O(log^2 n).
+#[derive(Clone)]
+pub struct LiChaoTree {
+    xs: Vec<i64>,
+    lines: Vec<Option<Line>>, // heap-ordered nodes, node 1 covers xs[0..n]
+}
+
+impl LiChaoTree {
+    pub fn new(xs: Vec<i64>) -> Self {
+        let xs = sorted_coordinates(xs);
+        let lines = vec![None; 4 * xs.len().max(1)];
+        Self { xs, lines }
+    }
+
+    pub fn add_line(&mut self, k: i64, m: i64) {
+        self.add_segment(k, m, ..);
+    }
+
+    /// Adds `y = k * x + m` for `x` in `range` only.
+    pub fn add_segment(&mut self, k: i64, m: i64, range: impl RangeBounds<i64>) {
+        let (begin, end) = index_range(&self.xs, range);
+        if begin < end {
+            self.insert_segment(1, 0, self.xs.len(), begin, end, (k, m));
+        }
+    }
+
+    /// `x` must be one of the coordinates given to `new`.
+    pub fn query(&self, x: i64) -> Option<i64> {
+        let i = self.xs.binary_search(&x).expect("x is not a query coordinate");
+        let (mut node, mut lo, mut hi) = (1, 0, self.xs.len());
+        let mut result = None;
+        loop {
+            if let Some(line) = self.lines[node] {
+                result = result.max(Some(eval(line, x)));
+            }
+            if hi - lo == 1 { return result; }
+            let mid = (lo + hi) / 2;
+            if i < mid {
+                (node, hi) = (2 * node, mid);
+            } else {
+                (node, lo) = (2 * node + 1, mid);
+            }
+        }
+    }
+
+    fn insert_segment(&mut self, node: usize, lo: usize, hi: usize, begin: usize, end: usize, line: Line) {
+        if end <= lo || hi <= begin { return; }
+        if begin <= lo && hi <= end {
+            self.insert_line(node, lo, hi, line);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.insert_segment(2 * node, lo, mid, begin, end, line);
+        self.insert_segment(2 * node + 1, mid, hi, begin, end, line);
+    }
+
+    fn insert_line(&mut self, mut node: usize, mut lo: usize, mut hi: usize, mut line: Line) {
+        loop {
+            let Some(current) = self.lines[node] else {
+                self.lines[node] = Some(line);
+                return;
+            };
+            let mid = (lo + hi) / 2;
+            let (lower, upper) = if eval(line, self.xs[mid]) > eval(current, self.xs[mid]) {
+                (current, line)
+            } else {
+                (line, current)
+            };
+            self.lines[node] = Some(upper);
+            line = lower;
+            if hi - lo == 1 { return; }
+            if eval(line, self.xs[lo]) > eval(upper, self.xs[lo]) {
+                (node, hi) = (2 * node, mid);
+            } else if eval(line, self.xs[hi - 1]) > eval(upper, self.xs[hi - 1]) {
+                (node, lo) = (2 * node + 1, mid);
+            } else {
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Node {
+    line: Option<Line>,
+    children: [usize; 2],
+}
+
+/// Fully persistent `LiChaoTree`; a version is identified by its root node.
+/// Version `0` has no lines.
+#[derive(Clone)]
+pub struct PersistentLiChaoTree {
+    xs: Vec<i64>,
+    nodes: Arena<Node>, // node 0 is the empty node and its own child
+}
+
+impl PersistentLiChaoTree {
+    pub const EMPTY: usize = 0;
+
+    pub fn new(xs: Vec<i64>) -> Self {
+        let xs = sorted_coordinates(xs);
+        let mut nodes = Arena::new();
+        nodes.alloc(Node::default());
+        Self { xs, nodes }
+    }
+
+    /// Returns the version obtained by adding `y = k * x + m` to `version`.
+    pub fn add_line(&mut self, version: usize, k: i64, m: i64) -> usize {
+        self.add_segment(version, k, m, ..)
+    }
+
+    pub fn add_segment(&mut self, version: usize, k: i64, m: i64, range: impl RangeBounds<i64>) -> usize {
+        let (begin, end) = index_range(&self.xs, range);
+        if begin == end { return version; }
+        self.insert_segment(version, 0, self.xs.len(), begin, end, (k, m))
+    }
+
+    /// `x` must be one of the coordinates given to `new`.
+    pub fn query(&self, version: usize, x: i64) -> Option<i64> {
+        let i = self.xs.binary_search(&x).expect("x is not a query coordinate");
+        let (mut node, mut lo, mut hi) = (version, 0, self.xs.len());
+        let mut result = None;
+        while node != 0 {
+            if let Some(line) = self.nodes[node].line {
+                result = result.max(Some(eval(line, x)));
+            }
+            let mid = (lo + hi) / 2;
+            if i < mid {
+                (node, hi) = (self.nodes[node].children[0], mid);
+            } else {
+                (node, lo) = (self.nodes[node].children[1], mid);
+            }
+        }
+        result
+    }
+
+    fn insert_segment(&mut self, node: usize, lo: usize, hi: usize, begin: usize, end: usize, line: Line) -> usize {
+        if end <= lo || hi <= begin { return node; }
+        if begin <= lo && hi <= end {
+            return self.insert_line(node, lo, hi, line);
+        }
+        let mid = (lo + hi) / 2;
+        let [left, right] = self.nodes[node].children;
+        let left = self.insert_segment(left, lo, mid, begin, end, line);
+        let right = self.insert_segment(right, mid, hi, begin, end, line);
+        self.nodes.alloc(Node { children: [left, right], ..self.nodes[node] })
+    }
+
+    fn insert_line(&mut self, node: usize, lo: usize, hi: usize, line: Line) -> usize {
+        let mut copy = self.nodes[node];
+        let Some(current) = copy.line else {
+            copy.line = Some(line);
+            return self.nodes.alloc(copy);
+        };
+        let mid = (lo + hi) / 2;
+        let (lower, upper) = if eval(line, self.xs[mid]) > eval(current, self.xs[mid]) {
+            (current, line)
+        } else {
+            (line, current)
+        };
+        copy.line = Some(upper);
+        if hi - lo > 1 {
+            if eval(lower, self.xs[lo]) > eval(upper, self.xs[lo]) {
+                copy.children[0] = self.insert_line(copy.children[0], lo, mid, lower);
+            } else if eval(lower, self.xs[hi - 1]) > eval(upper, self.xs[hi - 1]) {
+                copy.children[1] = self.insert_line(copy.children[1], mid, hi, lower);
+            }
+        }
+        self.nodes.alloc(copy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    fn random_segment(rng: &mut XorShift64) -> (i64, i64, i64, i64) {
+        let k = rng.gen_range(0..41) as i64 - 20;
+        let m = rng.gen_range(0..201) as i64 - 100;
+        let l = rng.gen_range(0..61) as i64 - 30;
+        let r = rng.gen_range(0..61) as i64 - 30;
+        (k, m, l, r)
+    }
+
+    fn naive(segments: &[(i64, i64, i64, i64)], x: i64) -> Option<i64> {
+        segments.iter().filter(|&&(_, _, l, r)| l <= x && x < r).map(|&(k, m, _, _)| k * x + m).max()
+    }
+
+    #[test]
+    fn test_li_chao_against_naive() {
+        let mut rng = XorShift64::new(13);
+        let xs: Vec<i64> = (-30..30).step_by(3).chain([29, 7, -30]).collect();
+        for _ in 0..30 {
+            let mut tree = LiChaoTree::new(xs.clone());
+            let mut segments = Vec::new();
+            for step in 0..30 {
+                let (k, m, l, r) = random_segment(&mut rng);
+                if step % 4 == 0 {
+                    tree.add_line(k, m);
+                    segments.push((k, m, i64::MIN, i64::MAX));
+                } else {
+                    tree.add_segment(k, m, l..r);
+                    segments.push((k, m, l, r));
+                }
+                for &x in &xs {
+                    assert_eq!(tree.query(x), naive(&segments, x));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_persistent_li_chao_keeps_old_versions() {
+        let mut rng = XorShift64::new(14);
+        let xs: Vec<i64> = (-30..30).collect();
+        let mut tree = PersistentLiChaoTree::new(xs.clone());
+        let mut versions = vec![PersistentLiChaoTree::EMPTY];
+        let mut segments = Vec::new();
+        for _ in 0..40 {
+            let (k, m, l, r) = random_segment(&mut rng);
+            // branch off a random earlier version
+            let parent = rng.gen_range(0..versions.len());
+            let mut contents: Vec<_> = segments.get(parent.wrapping_sub(1)).cloned().unwrap_or_default();
+            contents.push((k, m, l, r));
+            versions.push(tree.add_segment(versions[parent], k, m, l..r));
+            segments.push(contents);
+        }
+        for (v, contents) in versions.iter().skip(1).zip(&segments) {
+            for &x in &xs {
+                assert_eq!(tree.query(*v, x), naive(contents, x));
+            }
+        }
+        assert_eq!(tree.query(PersistentLiChaoTree::EMPTY, 0), None);
+    }
+}
\ No newline at end of file
@@ -1,6 +1,63 @@
 pub mod fenwick;
 pub mod segment_tree;
-pub mod mod_nat;
+pub mod modint;
 pub mod graph;
 pub mod disjoint_set;
-pub mod grid;
\ No newline at end of file
+pub mod grid;
+pub mod range;
+pub mod rng;
+pub mod naive;
+pub mod tree;
+pub mod sqrt_rebuild;
+pub mod removable_heap;
+pub mod top_k;
+pub mod running_median;
+pub mod slope_trick;
+pub mod dp_optimize;
+pub mod matroid;
+pub mod binary_trie;
+pub mod distinct;
+pub mod imos;
+pub mod monoid;
+pub mod point_segment_tree;
+pub mod queue_undo;
+pub mod convex_hull_trick;
+pub mod lazy_segment_tree;
+pub mod suffix_array;
+pub mod ordered_map;
+pub mod fast_hash;
+pub mod counter;
+pub mod segment_tree_2d;
+pub mod default_map;
+pub mod running_fold;
+pub mod intervals;
+pub mod flat_deque;
+pub mod arena;
+pub mod iter_ext;
+pub mod argsort;
+pub mod sparse_table;
+pub mod radix_sort;
+pub mod cast;
+pub mod event_sweep;
+pub mod rectangle_union;
+pub mod manhattan;
+pub mod probability;
+pub mod compressed_fenwick;
+pub mod float_matrix;
+pub mod matrix;
+pub mod game;
+pub mod de_bruijn;
+pub mod permutation;
+pub mod output;
+pub mod combinatorics;
+pub mod number_theory;
+pub mod pack;
+pub mod li_chao;
+pub mod ntt;
+pub mod fps;
+pub mod zobrist;
+pub mod time_segments;
+pub mod sieve;
+
+// Old path, kept so existing solutions keep compiling.
+pub use modint as mod_nat;
\ No newline at end of file
@@ -0,0 +1,77 @@
+use crate::grid::Grid;
+
+/// Rotates by 45 degrees: the Manhattan distance between two points equals the
+/// Chebyshev distance between their images, `max(|du|, |dv|)`.
+pub fn to_chebyshev(x: i64, y: i64) -> (i64, i64) {
+    (x + y, x - y)
+}
+
+/// Inverse of `to_chebyshev`; `u` and `v` must have the same parity.
+pub fn from_chebyshev(u: i64, v: i64) -> (i64, i64) {
+    ((u + v) / 2, (u - v) / 2)
+}
+
+/// Manhattan distance from every cell to the nearest `true` cell (ignoring walls),
+/// by a forward and a backward min-plus pass. O(HW).
+pub fn distance_transform(sources: &Grid<bool>) -> Grid<Option<usize>> {
+    let (height, width) = sources.size();
+    let mut dist = Grid::new((height, width), usize::MAX);
+    for i in 0..height {
+        for j in 0..width {
+            if sources.row(i)[j] {
+                dist.row_mut(i)[j] = 0;
+                continue;
+            }
+            let mut best = usize::MAX;
+            if i > 0 { best = best.min(dist.row(i - 1)[j].saturating_add(1)); }
+            if j > 0 { best = best.min(dist.row(i)[j - 1].saturating_add(1)); }
+            dist.row_mut(i)[j] = best;
+        }
+    }
+    for i in (0..height).rev() {
+        for j in (0..width).rev() {
+            let mut best = dist.row(i)[j];
+            if i + 1 < height { best = best.min(dist.row(i + 1)[j].saturating_add(1)); }
+            if j + 1 < width { best = best.min(dist.row(i)[j + 1].saturating_add(1)); }
+            dist.row_mut(i)[j] = best;
+        }
+    }
+    (0..height).map(|i| dist.row(i).iter().map(|&d| (d != usize::MAX).then_some(d)).collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_chebyshev_rotation() {
+        let points = [(0, 0), (3, -1), (-2, 5)];
+        for &(x1, y1) in &points {
+            for &(x2, y2) in &points {
+                let ((u1, v1), (u2, v2)) = (to_chebyshev(x1, y1), to_chebyshev(x2, y2));
+                assert_eq!((x1 - x2).abs() + (y1 - y2).abs(), (u1 - u2).abs().max((v1 - v2).abs()));
+                assert_eq!(from_chebyshev(u1, v1), (x1, y1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_transform() {
+        let mut rng = XorShift64::new(13);
+        let (height, width) = (7, 9);
+        let sources: Grid<bool> = (0..height).map(|_| (0..width).map(|_| rng.gen_range(0..10) == 0).collect()).collect();
+        let dist = distance_transform(&sources);
+        for i in 0..height {
+            for j in 0..width {
+                let expected = (0..height)
+                    .flat_map(|a| (0..width).map(move |b| (a, b)))
+                    .filter(|&(a, b)| sources.row(a)[b])
+                    .map(|(a, b)| a.abs_diff(i) + b.abs_diff(j))
+                    .min();
+                assert_eq!(dist.row(i)[j], expected);
+            }
+        }
+        assert_eq!(distance_transform(&Grid::new((2, 2), false)).row(1)[1], None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,146 @@
+use std::ops::{Add, Index, IndexMut, Mul};
+
+use crate::modint::Mod;
+use crate::monoid::pow_monoid;
+
+/// Semiring elements a `Matrix` can hold: plain integers and `Mod<N>`.
+pub trait MatrixElement: Copy + Add<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_matrix_element {
+    ($($t:ty),*) => {$(
+        impl MatrixElement for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+    )*};
+}
+
+impl_matrix_element!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+impl<const N: usize> MatrixElement for Mod<N> {
+    const ZERO: Self = Mod { value: 0 };
+    const ONE: Self = Mod { value: 1 };
+}
+
+/// Dense row-major matrix.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T> where
+    T: MatrixElement {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![T::ZERO; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut result = Self::new(n, n);
+        for i in 0..n {
+            result[(i, i)] = T::ONE;
+        }
+        result
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `self^exp` by repeated squaring, O(n^3 log exp).
+    pub fn pow(&self, exp: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "only square matrices have powers");
+        pow_monoid(self.clone(), exp, Self::identity(self.rows), |x, y| x * y)
+    }
+
+    /// The column vector `self * v`.
+    pub fn apply(&self, v: &[T]) -> Vec<T> {
+        assert_eq!(v.len(), self.cols);
+        (0..self.rows)
+            .map(|i| v.iter().enumerate().fold(T::ZERO, |acc, (j, &x)| acc + self[(i, j)] * x))
+            .collect()
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for Matrix<T> where
+    T: MatrixElement {
+    fn from(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == cols), "rows must have equal length");
+        Self { rows: rows.len(), cols, data: rows.into_iter().flatten().collect() }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.rows && j < self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.rows && j < self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T> Mul for &Matrix<T> where
+    T: MatrixElement {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Matrix<T> {
+        assert_eq!(self.cols, rhs.rows);
+        let mut result = Matrix::new(self.rows, rhs.cols);
+        // i-k-j order walks both `rhs` and `result` row by row.
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..rhs.cols {
+                    result[(i, j)] = result[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fibonacci() {
+        type M = Mod<1_000_000_007>;
+        let step = Matrix::from(vec![vec![M::new(1), M::new(1)], vec![M::new(1), M::new(0)]]);
+        // (F(n+1), F(n)) = step^n (F(1), F(0))
+        let fib = |n| step.pow(n).apply(&[M::new(1), M::new(0)])[1].value;
+        assert_eq!(fib(0), 0);
+        assert_eq!(fib(10), 55);
+        assert_eq!(fib(1000), 517_691_607);
+
+        let step = Matrix::from(vec![vec![1u64, 1], vec![1, 0]]);
+        assert!(step.pow(90) == &step.pow(45) * &step.pow(45));
+        assert_eq!(step.pow(90)[(0, 1)], 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn test_rectangular() {
+        let a = Matrix::from(vec![vec![1i64, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from(vec![vec![1i64], vec![0], vec![-1]]);
+        let product = &a * &b;
+        assert_eq!((product.rows(), product.cols()), (2, 1));
+        assert_eq!(product.apply(&[1]), [-2, -2]);
+        assert!(&Matrix::identity(2) * &a == a);
+    }
+}
\ No newline at end of file
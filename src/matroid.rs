@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+
+use crate::disjoint_set::DisjointSet;
+
+pub trait Matroid {
+    /// Number of elements in the ground set.
+    fn size(&self) -> usize;
+    fn is_independent(&self, set: &[bool]) -> bool;
+}
+
+/// Ground set: edges; independent sets: forests.
+#[derive(Clone)]
+pub struct GraphicMatroid {
+    n: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl GraphicMatroid {
+    pub fn new(n: usize, edges: Vec<(usize, usize)>) -> Self {
+        Self { n, edges }
+    }
+}
+
+impl Matroid for GraphicMatroid {
+    fn size(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn is_independent(&self, set: &[bool]) -> bool {
+        let mut dsu = DisjointSet::new(self.n);
+        for (&(u, v), _) in self.edges.iter().zip(set).filter(|(_, &chosen)| chosen) {
+            if dsu.find(u) == dsu.find(v) { return false; }
+            dsu.union(u, v);
+        }
+        true
+    }
+}
+
+/// Element `i` has color `colors[i]`; independent sets take at most
+/// `capacities[c]` elements of each color `c`.
+#[derive(Clone)]
+pub struct PartitionMatroid {
+    colors: Vec<usize>,
+    capacities: Vec<usize>,
+}
+
+impl PartitionMatroid {
+    pub fn new(colors: Vec<usize>, capacities: Vec<usize>) -> Self {
+        Self { colors, capacities }
+    }
+}
+
+impl Matroid for PartitionMatroid {
+    fn size(&self) -> usize {
+        self.colors.len()
+    }
+
+    fn is_independent(&self, set: &[bool]) -> bool {
+        let mut counts = vec![0; self.capacities.len()];
+        for (&color, _) in self.colors.iter().zip(set).filter(|(_, &chosen)| chosen) {
+            counts[color] += 1;
+            if counts[color] > self.capacities[color] { return false; }
+        }
+        true
+    }
+}
+
+/// A maximum common independent set, as sorted element indices.
+/// O(r^2 n) oracle calls for answer size r.
+pub fn matroid_intersection(m1: &impl Matroid, m2: &impl Matroid) -> Vec<usize> {
+    let n = m1.size();
+    assert_eq!(n, m2.size(), "matroids must share the ground set");
+    let mut chosen = vec![false; n];
+    while let Some(path) = augmenting_path(m1, m2, &mut chosen) {
+        for v in path {
+            chosen[v] = !chosen[v];
+        }
+    }
+    (0..n).filter(|&v| chosen[v]).collect()
+}
+
+/// Shortest path in the exchange graph from `I + y ∈ M1` to `I + y ∈ M2`.
+fn augmenting_path(m1: &impl Matroid, m2: &impl Matroid, chosen: &mut [bool]) -> Option<Vec<usize>> {
+    let n = chosen.len();
+    let mut is_sink = vec![false; n];
+    let mut queue = VecDeque::new();
+    let mut prev = vec![None; n];
+    let mut visited = vec![false; n];
+    for y in 0..n {
+        if chosen[y] { continue; }
+        chosen[y] = true;
+        if m1.is_independent(chosen) {
+            visited[y] = true;
+            queue.push_back(y);
+        }
+        is_sink[y] = m2.is_independent(chosen);
+        chosen[y] = false;
+    }
+    while let Some(u) = queue.pop_front() {
+        if is_sink[u] {
+            let mut path = vec![u];
+            let mut current = u;
+            while let Some(p) = prev[current] {
+                path.push(p);
+                current = p;
+            }
+            return Some(path);
+        }
+        for v in 0..n {
+            if visited[v] || chosen[u] == chosen[v] { continue; }
+            // x in I, y not in I: x -> y if I - x + y ∈ M1, y -> x if I - x + y ∈ M2.
+            let u_chosen = chosen[u];
+            let (x, y) = if u_chosen { (u, v) } else { (v, u) };
+            chosen[x] = false;
+            chosen[y] = true;
+            let exchangeable = if u_chosen { m1.is_independent(chosen) } else { m2.is_independent(chosen) };
+            chosen[x] = true;
+            chosen[y] = false;
+            if exchangeable {
+                visited[v] = true;
+                prev[v] = Some(u);
+                queue.push_back(v);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bipartite_matching() {
+        // Edge i joins left[i] and right[i]; a matching uses each side at most once.
+        let left = vec![0, 0, 1, 2, 2, 3];
+        let right = vec![0, 1, 0, 1, 2, 2];
+        let m1 = PartitionMatroid::new(left, vec![1; 4]);
+        let m2 = PartitionMatroid::new(right, vec![1; 3]);
+        assert_eq!(matroid_intersection(&m1, &m2).len(), 3);
+    }
+
+    #[test]
+    fn test_colorful_spanning_forest() {
+        // Spanning tree of a 4-cycle with a chord using at most one edge of each color.
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)];
+        let colors = vec![0, 0, 1, 1, 2];
+        let graphic = GraphicMatroid::new(4, edges.clone());
+        let partition = PartitionMatroid::new(colors.clone(), vec![1; 3]);
+        let result = matroid_intersection(&graphic, &partition);
+
+        assert_eq!(result.len(), 3);
+        let mut chosen = vec![false; edges.len()];
+        for &e in &result {
+            chosen[e] = true;
+        }
+        assert!(graphic.is_independent(&chosen));
+        assert!(partition.is_independent(&chosen));
+    }
+}
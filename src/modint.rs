@@ -0,0 +1,412 @@
+/// Residue modulo `N`, for `2 <= N <= 2^62`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mod<const N: usize> {
+    pub value: usize
+}
+
+impl<const N: usize> Mod<N> {
+    const _N_NOT_ZERO_OR_ONE: usize = 1 / ((N >= 2) as usize);
+
+    pub fn new(value: usize) -> Self {
+        Self { value: value % N }
+    }
+
+    pub fn pow(self, mut nth: usize) -> Self {
+        let mut result = Self::new(1);
+        let mut base = self;
+        while nth > 0 {
+            if nth % 2 == 1 {
+                result *= base;
+            }
+            base *= base;
+            nth /= 2;
+        }
+        result
+    }
+
+    /// Multiplicative inverse by Fermat's little theorem; `N` must be prime and `self` nonzero.
+    pub fn inv(self) -> Self {
+        assert!(self.value != 0, "zero has no inverse");
+        self.pow(N - 2)
+    }
+
+    /// Inverses of all of `values` with a single `inv`; none may be zero.
+    pub fn batch_inverse(values: &[Self]) -> Vec<Self> {
+        let mut prefix = Vec::with_capacity(values.len());
+        let mut acc = Self::new(1);
+        for &v in values {
+            prefix.push(acc);
+            acc *= v;
+        }
+        let mut inv = acc.inv();
+        let mut result = vec![Self::new(0); values.len()];
+        for (i, &v) in values.iter().enumerate().rev() {
+            result[i] = inv * prefix[i];
+            inv *= v;
+        }
+        result
+    }
+}
+
+impl<const N: usize> std::ops::Add for Mod<N> {
+    type Output = Mod<N>;
+    fn add(self, rhs: Self) -> Self::Output {
+        let value = self.value + rhs.value;
+        Self { value: if value >= N { value - N } else { value } }
+    }
+}
+
+impl<const N: usize> std::ops::AddAssign for Mod<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> std::ops::Sub for Mod<N> {
+    type Output = Mod<N>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let value = if self.value >= rhs.value { self.value - rhs.value } else { self.value + N - rhs.value };
+        Self { value }
+    }
+}
+
+impl<const N: usize> std::ops::SubAssign for Mod<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> std::ops::Neg for Mod<N> {
+    type Output = Mod<N>;
+    fn neg(self) -> Self::Output {
+        Self::new(0) - self
+    }
+}
+
+impl<const N: usize> std::ops::Mul for Mod<N> {
+    type Output = Mod<N>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        // `% N` on a u64 product lowers to multiplications, but on a u128 product it calls
+        // `__umodti3`, so moduli above 2^32 go through Barrett reduction instead.
+        if N <= 1 << 32 {
+            Self::new(self.value * rhs.value)
+        } else {
+            Self { value: Barrett::<N>::reduce(self.value as u128 * rhs.value as u128) as usize }
+        }
+    }
+}
+
+/// Barrett reduction for `2^32 < N <= 2^62`, with `K` the bit length of `N`:
+/// `q = ((t >> (K - 1)) * MU) >> (K + 1)` with `MU = 2^2K / N` undercounts `t / N` by at most 2,
+/// and both factors fit in u64. Only `N = 2^62` makes `MU` wrap to 0; then `q = 0` and the
+/// low 64 bits of `t`, already below `4N`, are reduced by the final corrections alone.
+struct Barrett<const N: usize>;
+
+impl<const N: usize> Barrett<N> {
+    const K: u32 = usize::BITS - N.leading_zeros();
+    const MU: u64 = ((1u128 << (2 * Self::K)) / N as u128) as u64;
+
+    /// `t mod N`, for `t < N^2`.
+    #[inline]
+    fn reduce(t: u128) -> u64 {
+        let q = ((t >> (Self::K - 1)) as u64 as u128 * Self::MU as u128) >> (Self::K + 1);
+        let r = (t as u64).wrapping_sub((q as u64).wrapping_mul(N as u64));
+        let r = if r >= 2 * N as u64 { r - 2 * N as u64 } else { r };
+        if r >= N as u64 { r - N as u64 } else { r }
+    }
+}
+
+impl<const N: usize> std::ops::MulAssign for Mod<N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> std::ops::Div for Mod<N> {
+    type Output = Mod<N>;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl<const N: usize> std::ops::DivAssign for Mod<N> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const N: usize> Default for Mod<N> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl<const N: usize> From<$t> for Mod<N> {
+            fn from(value: $t) -> Self {
+                Self { value: (value as u128 % N as u128) as usize }
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned!(u32, u64, usize);
+
+impl<const N: usize> From<i64> for Mod<N> {
+    fn from(value: i64) -> Self {
+        Self { value: (value as i128).rem_euclid(N as i128) as usize }
+    }
+}
+
+impl<const N: usize> std::iter::Sum for Mod<N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), |acc, x| acc + x)
+    }
+}
+
+impl<'a, const N: usize> std::iter::Sum<&'a Mod<N>> for Mod<N> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<const N: usize> std::iter::Product for Mod<N> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |acc, x| acc * x)
+    }
+}
+
+impl<'a, const N: usize> std::iter::Product<&'a Mod<N>> for Mod<N> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl<const N: usize> std::fmt::Display for Mod<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// `dst[i] += src[i]` for each `i`.
+pub fn add_assign_slices<const N: usize>(dst: &mut [Mod<N>], src: &[Mod<N>]) {
+    assert_eq!(dst.len(), src.len());
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d += s;
+    }
+}
+
+/// `a[i] *= c` for each `i`.
+pub fn scale_slice<const N: usize>(a: &mut [Mod<N>], c: Mod<N>) {
+    for x in a.iter_mut() {
+        *x *= c;
+    }
+}
+
+/// Sum of `a[i] * b[i]`. For `N <= 2^32` the products are summed in `u64` and reduced once
+/// per block, as many as fit without overflow, which keeps the inner loop free of `%`.
+pub fn dot_product<const N: usize>(a: &[Mod<N>], b: &[Mod<N>]) -> Mod<N> {
+    assert_eq!(a.len(), b.len());
+    if N > 1 << 32 {
+        return a.iter().zip(b).map(|(&x, &y)| x * y).sum();
+    }
+    let block = (u64::MAX / ((N as u64 - 1) * (N as u64 - 1))) as usize;
+    let mut result = 0;
+    for (xs, ys) in a.chunks(block).zip(b.chunks(block)) {
+        let sum = xs.iter().zip(ys).fold(0u64, |acc, (x, y)| acc + x.value as u64 * y.value as u64);
+        result = (result + sum % N as u64) % N as u64;
+    }
+    Mod::new(result as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_modulation() {
+        let a: Mod<7> = Mod::new(10);
+        // 10 mod 7 = 3
+        assert_eq!(a.value, 3);
+    }
+
+    #[test]
+    fn test_add() {
+        let a: Mod<7> = Mod::new(3);
+        let b: Mod<7> = Mod::new(5);
+        let c = a + b;
+        // (3 + 5) mod 7 = 8 mod 7 = 1
+        assert_eq!(c.value, 1);
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut a: Mod<7> = Mod::new(3);
+        let b: Mod<7> = Mod::new(5);
+        a += b;
+        // (3 + 5) mod 7 = 1
+        assert_eq!(a.value, 1);
+    }
+
+    #[test]
+    fn test_mul() {
+        let a: Mod<7> = Mod::new(3);
+        let b: Mod<7> = Mod::new(5);
+        let c = a * b;
+        // (3 * 5) mod 7 = 15 mod 7 = 1
+        assert_eq!(c.value, 1);
+    }
+
+    #[test]
+    fn test_mul_assign() {
+        let mut a: Mod<7> = Mod::new(3);
+        let b: Mod<7> = Mod::new(5);
+        a *= b;
+        // (3 * 5) mod 7 = 1
+        assert_eq!(a.value, 1);
+    }
+
+    #[test]
+    fn test_pow_zero_exponent() {
+        let a: Mod<7> = Mod::new(3);
+        let b = a.pow(0);
+        // any number^0 mod N should be 1
+        assert_eq!(b.value, 1);
+    }
+
+    #[test]
+    fn test_pow_small_exponent() {
+        let a: Mod<13> = Mod::new(2);
+        let b = a.pow(3);
+        // 2^3 = 8 mod 13 = 8
+        assert_eq!(b.value, 8);
+    }
+
+    #[test]
+    fn test_pow_large_exponent() {
+        let a: Mod<1_000_003> = Mod::new(7);
+        let b = a.pow(100);
+        // Calculate expected result by iterated multiplication
+        let mut expected = 1;
+        for _ in 0..100 {
+            expected = (expected * 7) % 1_000_003;
+        }
+        assert_eq!(b.value, expected);
+    }
+
+    #[test]
+    fn test_sub_neg_div() {
+        let a: Mod<7> = Mod::new(3);
+        let b: Mod<7> = Mod::new(5);
+        // (3 - 5) mod 7 = 5
+        assert_eq!((a - b).value, 5);
+        assert_eq!((-a).value, 4);
+        assert_eq!((-Mod::<7>::new(0)).value, 0);
+        // 3 * 5^{-1} = 3 * 3 = 9 mod 7 = 2
+        assert_eq!((a / b).value, 2);
+        assert_eq!((b.inv() * b).value, 1);
+    }
+
+    #[test]
+    fn test_sub_assign_and_neg_with_composite_modulus() {
+        let mut a: Mod<12> = Mod::new(4);
+        a -= Mod::new(9);
+        // (4 - 9) mod 12 = 7
+        assert_eq!(a.value, 7);
+        a -= a;
+        assert_eq!(a.value, 0);
+        assert_eq!((-Mod::<12>::new(5)).value, 7);
+        assert_eq!((Mod::<12>::new(3) - Mod::new(11) + Mod::new(8)).value, 0);
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let values: Vec<Mod<998_244_353>> = (1..50).map(|x| Mod::new(x * x + 7)).collect();
+        let inverses = Mod::batch_inverse(&values);
+        for (v, inv) in values.iter().zip(&inverses) {
+            assert_eq!(inv.value, v.inv().value);
+        }
+        assert!(Mod::<7>::batch_inverse(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_conversions_and_folds() {
+        assert_eq!(Mod::<7>::from(-1i64).value, 6);
+        assert_eq!(Mod::<7>::from(i64::MIN).value, (i64::MIN as i128).rem_euclid(7) as usize);
+        assert_eq!(Mod::<7>::from(u64::MAX).value, (u64::MAX % 7) as usize);
+        assert_eq!(Mod::<7>::from(10u32).value, 3);
+        assert_eq!(Mod::<7>::default().value, 0);
+
+        let values = [3u64, 5, 6];
+        let sum: Mod<7> = values.iter().map(|&x| Mod::from(x)).sum();
+        assert_eq!(sum.value, 0);
+        let product: Mod<7> = values.iter().map(|&x| Mod::<7>::from(x)).collect::<Vec<_>>().iter().product();
+        assert_eq!(product.value, 6);
+    }
+
+    #[test]
+    fn test_mul_matches_naive() {
+        fn check<const N: usize>(rng: &mut crate::rng::XorShift64) {
+            for _ in 0..10000 {
+                let a = rng.gen_range(0..N);
+                let b = rng.gen_range(0..N);
+                let expected = (a as u128 * b as u128 % N as u128) as usize;
+                assert_eq!((Mod::<N>::new(a) * Mod::new(b)).value, expected);
+            }
+            assert_eq!((Mod::<N>::new(N - 1) * Mod::new(N - 1)).value, 1);
+        }
+        let mut rng = crate::rng::XorShift64::new(3);
+        check::<2>(&mut rng);
+        check::<998_244_353>(&mut rng);
+        check::<1_000_000_007>(&mut rng);
+        check::<2_147_483_647>(&mut rng);
+        check::<4_294_967_291>(&mut rng);
+        check::<4_294_967_311>(&mut rng);
+        check::<2_305_843_009_213_693_951>(&mut rng);
+        check::<6_000_000_000>(&mut rng);
+        check::<{ (1 << 62) - 1 }>(&mut rng);
+        check::<{ 1 << 62 }>(&mut rng);
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        const P: usize = 2_305_843_009_213_693_951; // 2^61 - 1
+        let a: Mod<P> = Mod::new(P - 2);
+        assert_eq!((a * a).value, 4);
+        assert_eq!((a.inv() * a).value, 1);
+        assert_eq!((a + a).value, P - 4);
+        assert_eq!(Mod::<P>::new(2).pow(61).value, 1);
+    }
+
+    #[test]
+    fn test_slice_operations() {
+        fn check<const N: usize>(rng: &mut crate::rng::XorShift64) {
+            let a: Vec<Mod<N>> = (0..1000).map(|_| Mod::new(rng.gen_range(0..N))).collect();
+            let b: Vec<Mod<N>> = (0..1000).map(|_| Mod::new(rng.gen_range(0..N))).collect();
+            let expected = a.iter().zip(&b).map(|(&x, &y)| x * y).sum::<Mod<N>>();
+            assert_eq!(dot_product(&a, &b).value, expected.value);
+
+            let mut c = a.clone();
+            add_assign_slices(&mut c, &b);
+            scale_slice(&mut c, Mod::new(N - 1));
+            for ((x, y), z) in a.iter().zip(&b).zip(&c) {
+                assert_eq!((-(*x + *y)).value, z.value);
+            }
+        }
+        let mut rng = crate::rng::XorShift64::new(4);
+        check::<2>(&mut rng);
+        check::<998_244_353>(&mut rng);
+        check::<4_294_967_291>(&mut rng);
+        check::<{ 1 << 32 }>(&mut rng);
+        check::<2_305_843_009_213_693_951>(&mut rng);
+        assert_eq!(dot_product::<7>(&[], &[]).value, 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let a: Mod<7> = Mod::new(10); // 10 mod 7 = 3
+        assert_eq!(a.to_string(), "3");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,241 @@
+use std::ops::{Add, Mul};
+
+use crate::segment_tree::{Affine, Assign, SegmentTreeCompatible};
+
+pub trait Monoid: Copy {
+    /// ident().combine(x) == x && x.combine(ident()) == x
+    fn ident() -> Self;
+    /// x.combine(y).combine(z) == x.combine(y.combine(z))
+    fn combine(self, rhs: Self) -> Self;
+}
+
+impl Monoid for usize {
+    fn ident() -> Self { 0 }
+    fn combine(self, rhs: Self) -> Self { self.max(rhs) }
+}
+
+
+/// `base` combined with itself `nth` times by repeated squaring, for any associative `op`
+/// with identity `ident` (permutations, matrices, strings of operations, ...). O(log nth) ops.
+pub fn pow_monoid<T>(base: T, mut nth: u64, ident: T, op: impl Fn(&T, &T) -> T) -> T {
+    let mut result = ident;
+    let mut base = base;
+    while nth > 0 {
+        if nth % 2 == 1 {
+            result = op(&result, &base);
+        }
+        nth /= 2;
+        if nth > 0 {
+            base = op(&base, &base);
+        }
+    }
+    result
+}
+
+/// Primitive integers usable in the preset monoids below.
+pub trait Number: Copy + Ord + Add<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const MIN: Self;
+    const MAX: Self;
+    fn from_usize(n: usize) -> Self;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            const ZERO: Self = 0;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            fn from_usize(n: usize) -> Self { n as $t }
+        }
+    )*};
+}
+
+impl_number!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+/// Range max. `None` is the empty aggregate; as `SegmentTreeCompatible`, updates add to
+/// every covered element, `None` is the no-op update, and empty leaves stay empty.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct MaxMonoid<T>(pub Option<T>);
+
+impl<T> MaxMonoid<T> {
+    pub fn new(value: T) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl<T> Monoid for MaxMonoid<T> where
+    T: Number {
+    fn ident() -> Self { Self(None) }
+    fn combine(self, rhs: Self) -> Self { Self(self.0.max(rhs.0)) }
+}
+
+impl<T> SegmentTreeCompatible for MaxMonoid<T> where
+    T: Number {
+    fn ident() -> Self { Self(None) }
+    fn combine(self, rhs: Self) -> Self { Self(self.0.max(rhs.0)) }
+    fn apply(self, rhs: Self) -> Self { Self(self.0.map(|x| rhs.0.map_or(x, |y| x + y))) }
+    fn compose(self, rhs: Self) -> Self { Self(add_options(self.0, rhs.0)) }
+}
+
+/// Range min, with the same conventions as `MaxMonoid`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct MinMonoid<T>(pub Option<T>);
+
+impl<T> MinMonoid<T> {
+    pub fn new(value: T) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl<T> Monoid for MinMonoid<T> where
+    T: Number {
+    fn ident() -> Self { Self(None) }
+    fn combine(self, rhs: Self) -> Self {
+        match (self.0, rhs.0) {
+            (Some(x), Some(y)) => Self(Some(x.min(y))),
+            (x, y) => Self(x.or(y)),
+        }
+    }
+}
+
+impl<T> SegmentTreeCompatible for MinMonoid<T> where
+    T: Number {
+    fn ident() -> Self { Self(None) }
+    fn combine(self, rhs: Self) -> Self { Monoid::combine(self, rhs) }
+    fn apply(self, rhs: Self) -> Self { Self(self.0.map(|x| rhs.0.map_or(x, |y| x + y))) }
+    fn compose(self, rhs: Self) -> Self { Self(add_options(self.0, rhs.0)) }
+}
+
+fn add_options<T>(lhs: Option<T>, rhs: Option<T>) -> Option<T> where
+    T: Number {
+    match (lhs, rhs) {
+        (Some(x), Some(y)) => Some(x + y),
+        (x, y) => x.or(y),
+    }
+}
+
+/// Range sum. As `SegmentTreeCompatible`, updates add to every covered element.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SumMonoid<T>(pub T);
+
+impl<T> Monoid for SumMonoid<T> where
+    T: Number {
+    fn ident() -> Self { Self(T::ZERO) }
+    fn combine(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+}
+
+impl<T> SegmentTreeCompatible for SumMonoid<T> where
+    T: Number {
+    fn ident() -> Self { Self(T::ZERO) }
+    fn combine(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+    fn apply(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+    fn apply_len(self, rhs: Self, len: usize) -> Self { Self(self.0 + rhs.0 * T::from_usize(len)) }
+    fn compose(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+}
+
+/// Range gcd, with `gcd(0, x) == x`. A plain `Monoid` for `PointSegmentTree`; with
+/// `SegmentTree`, use `Assign<GcdMonoid>` for range assignment.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GcdMonoid(pub u64);
+
+impl Monoid for GcdMonoid {
+    fn ident() -> Self { Self(0) }
+    fn combine(self, rhs: Self) -> Self { Self(crate::number_theory::gcd(self.0, rhs.0)) }
+}
+
+/// Function composition, left map first, with range assignment of maps.
+pub type AffineComposeMonoid<T> = Assign<Affine<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modint::Mod;
+    use crate::point_segment_tree::PointSegmentTree;
+    use crate::segment_tree::SegmentTree;
+
+    #[test]
+    fn test_pow_monoid() {
+        // Applying the permutation `p` first, then `q`, is `compose(p, q)`.
+        let compose = |p: &Vec<usize>, q: &Vec<usize>| p.iter().map(|&i| q[i]).collect::<Vec<_>>();
+        let cycle = vec![1, 2, 3, 4, 0];
+        let identity: Vec<usize> = (0..5).collect();
+        assert_eq!(pow_monoid(cycle.clone(), 3, identity.clone(), compose), [3, 4, 0, 1, 2]);
+        assert_eq!(pow_monoid(cycle.clone(), 1_000_000_000_000, identity.clone(), compose), identity);
+
+        let repeated = pow_monoid("ab".to_string(), 3, String::new(), |a, b| format!("{}{}", a, b));
+        assert_eq!(repeated, "ababab");
+        assert_eq!(pow_monoid(7_u64, 0, 1, |a, b| a * b), 1);
+    }
+
+    #[test]
+    fn test_presets_with_segment_tree() {
+        let mut maxima: SegmentTree<MaxMonoid<i64>> = [-3, -1, -4].into_iter().map(MaxMonoid::new).collect();
+        maxima.update(1.., MaxMonoid::new(-5));
+        assert_eq!(maxima.query(..), MaxMonoid::new(-3));
+        assert_eq!(maxima.query(1..), MaxMonoid::new(-6));
+        maxima.update(.., MaxMonoid(None));
+        assert_eq!(maxima.query(1..), MaxMonoid::new(-6));
+        assert_eq!(maxima.query(1..1), MaxMonoid(None));
+
+        let mut minima: SegmentTree<MinMonoid<i64>> = [3, 1, 4].into_iter().map(MinMonoid::new).collect();
+        minima.update(..2, MinMonoid::new(5));
+        assert_eq!(minima.query(..), MinMonoid::new(4));
+
+        let mut sums: SegmentTree<SumMonoid<u64>> = [3, 1, 4].into_iter().map(SumMonoid).collect();
+        sums.update(.., SumMonoid(2));
+        assert_eq!(sums.query(1..), SumMonoid(9));
+    }
+
+    #[test]
+    fn test_unsigned_zero_leaves() {
+        let mut maxima: SegmentTree<MaxMonoid<u64>> = [0, 0, 0].into_iter().map(MaxMonoid::new).collect();
+        maxima.update(.., MaxMonoid::new(5));
+        assert_eq!(maxima.query(..), MaxMonoid::new(5));
+        maxima.update(1..2, MaxMonoid::new(0));
+        assert_eq!(maxima.to_vec(), [5, 5, 5].map(MaxMonoid::new));
+
+        let mut minima: SegmentTree<MinMonoid<u64>> = [0, u64::MAX - 1, 0].into_iter().map(MinMonoid::new).collect();
+        minima.update(1.., MinMonoid::new(1));
+        assert_eq!(minima.query(1..), MinMonoid::new(1));
+        assert_eq!(minima.query(..), MinMonoid::new(0));
+        // Leaves past the initial ones are empty and stay empty under updates.
+        let mut empty = SegmentTree::<MaxMonoid<u64>>::new(2);
+        empty.update(.., MaxMonoid::new(3));
+        assert_eq!(empty.query(..), MaxMonoid(None));
+    }
+
+    #[test]
+    fn test_assignment_presets_with_segment_tree() {
+        let mut gcds: SegmentTree<Assign<GcdMonoid>> = [12, 18, 8].into_iter().map(|x| Assign(Some(GcdMonoid(x)))).collect();
+        assert_eq!(gcds.query(..2), Assign(Some(GcdMonoid(6))));
+        gcds.update(1.., Assign(Some(GcdMonoid(0))));
+        assert_eq!(gcds.query(..), Assign(Some(GcdMonoid(12))));
+        gcds.update(..2, Assign(Some(GcdMonoid(9))));
+        assert_eq!(gcds.query(..), Assign(Some(GcdMonoid(9))));
+
+        type M = Mod<998_244_353>;
+        let f = Affine::new(M::new(2), M::new(1));
+        let g = Affine::new(M::new(3), M::new(0));
+        let mut composites: SegmentTree<AffineComposeMonoid<M>> = [f, g, g].into_iter().map(|h| Assign(Some(h))).collect();
+        assert_eq!(composites.query(..).0.unwrap().eval(M::new(5)).value, 99);
+        composites.update(1.., Assign(Some(f)));
+        assert_eq!(composites.query(..).0.unwrap().eval(M::new(5)).value, 47);
+    }
+
+    #[test]
+    fn test_presets_with_point_segment_tree() {
+        let gcds: PointSegmentTree<GcdMonoid> = [12, 18, 8, 7].into_iter().map(GcdMonoid).collect();
+        assert_eq!(gcds.query(..2), GcdMonoid(6));
+        assert_eq!(gcds.query(..3), GcdMonoid(2));
+        assert_eq!(gcds.query(..), GcdMonoid(1));
+
+        type M = Mod<998_244_353>;
+        let f = Affine::new(M::new(2), M::new(1));
+        let g = Affine::new(M::new(3), M::new(0));
+        let composites: PointSegmentTree<Affine<M>> = [f, g].into_iter().collect();
+        assert_eq!(composites.query(..).eval(M::new(5)).value, 33);
+        let maxima: PointSegmentTree<MaxMonoid<u32>> = [0, 0].into_iter().map(MaxMonoid::new).collect();
+        assert_eq!(maxima.query(..), MaxMonoid::new(0));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::ops::RangeBounds;
+
+use crate::fenwick::FenwickCompatible;
+use crate::range::to_half_open;
+use crate::segment_tree::SegmentTreeCompatible;
+
+/// Brute-force counterpart of the range structures, for stress tests.
+#[derive(Clone)]
+pub struct RangeArray<T> {
+    values: Vec<T>,
+}
+
+impl<T> RangeArray<T> where
+    T: Copy {
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T> RangeArray<T> where
+    T: SegmentTreeCompatible {
+    pub fn update(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let (begin, end) = to_half_open(range, self.len());
+        for x in &mut self.values[begin..end] {
+            *x = x.apply_len(value, 1);
+        }
+    }
+
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.len());
+        self.values[begin..end].iter().fold(T::ident(), |acc, &x| acc.combine(x))
+    }
+}
+
+impl<T> RangeArray<T> where
+    T: FenwickCompatible {
+    pub fn add(&mut self, range: impl RangeBounds<usize>, val: T) {
+        let (begin, end) = to_half_open(range, self.len());
+        for x in &mut self.values[begin..end] {
+            x.add_assign(val);
+        }
+    }
+
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.len());
+        self.values[begin..end].iter().fold(T::zero(), |acc, &x| acc.add(x))
+    }
+}
\ No newline at end of file
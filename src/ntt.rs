@@ -0,0 +1,193 @@
+use crate::modint::Mod;
+use crate::number_theory::{inv_mod, pow_mod};
+
+/// Smallest generator of the multiplicative group modulo a prime `p`.
+const fn primitive_root(p: u64) -> u64 {
+    let mut factors = [0; 64];
+    let mut count = 0;
+    let mut rest = p - 1;
+    let mut d = 2;
+    while d * d <= rest {
+        if rest % d == 0 {
+            factors[count] = d;
+            count += 1;
+            while rest % d == 0 {
+                rest /= d;
+            }
+        }
+        d += 1;
+    }
+    if rest > 1 {
+        factors[count] = rest;
+        count += 1;
+    }
+    let mut g = 2;
+    loop {
+        let mut i = 0;
+        while i < count && pow_mod(g, (p - 1) / factors[i], p) != 1 {
+            i += 1;
+        }
+        if i == count { return g; }
+        g += 1;
+    }
+}
+
+struct Root<const P: usize>;
+
+impl<const P: usize> Root<P> {
+    const G: usize = primitive_root(P as u64) as usize;
+}
+
+fn transform<const P: usize>(a: &mut [Mod<P>], inverse: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+    assert!((P - 1) % n == 0, "{} does not support length {}", P, n);
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let mut w = Mod::<P>::new(Root::<P>::G).pow((P - 1) / len);
+        if inverse {
+            w = w.inv();
+        }
+        let half = len / 2;
+        let mut powers = Vec::with_capacity(half);
+        let mut wn = Mod::new(1);
+        for _ in 0..half {
+            powers.push(wn);
+            wn *= w;
+        }
+        for chunk in a.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for ((x, y), &wn) in lo.iter_mut().zip(hi).zip(&powers) {
+                let (u, v) = (*x, *y * wn);
+                *x = u + v;
+                *y = u - v;
+            }
+        }
+        len <<= 1;
+    }
+    if inverse {
+        let n_inv = Mod::<P>::new(n).inv();
+        for x in a.iter_mut() {
+            *x *= n_inv;
+        }
+    }
+}
+
+/// In-place number theoretic transform; `P` must be a prime with `a.len()` dividing `P - 1`.
+pub fn ntt<const P: usize>(a: &mut [Mod<P>]) {
+    transform(a, false);
+}
+
+pub fn intt<const P: usize>(a: &mut [Mod<P>]) {
+    transform(a, true);
+}
+
+/// `c[k] = sum of a[i] * b[k - i]` modulo an NTT-friendly prime such as 998244353.
+pub fn convolution<const P: usize>(a: &[Mod<P>], b: &[Mod<P>]) -> Vec<Mod<P>> {
+    if a.is_empty() || b.is_empty() { return Vec::new(); }
+    let len = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= 32 {
+        let mut result = vec![Mod::new(0); len];
+        for (i, &x) in a.iter().enumerate() {
+            for (r, &y) in result[i..].iter_mut().zip(b) {
+                *r += x * y;
+            }
+        }
+        return result;
+    }
+    let size = len.next_power_of_two();
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(size, Mod::new(0));
+    fb.resize(size, Mod::new(0));
+    ntt(&mut fa);
+    ntt(&mut fb);
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x *= y;
+    }
+    intt(&mut fa);
+    fa.truncate(len);
+    fa
+}
+
+const P1: usize = 167_772_161;
+const P2: usize = 469_762_049;
+const P3: usize = 754_974_721;
+
+/// Convolution modulo any `modulus`, via three NTT primes and the Chinese remainder theorem.
+/// Exact while every true coefficient is below `P1 * P2 * P3` (about 2^86).
+pub fn convolution_mod(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    fn residues<const P: usize>(a: &[u64], b: &[u64]) -> Vec<Mod<P>> {
+        let fa: Vec<_> = a.iter().map(|&x| Mod::from(x)).collect();
+        let fb: Vec<_> = b.iter().map(|&x| Mod::from(x)).collect();
+        convolution(&fa, &fb)
+    }
+    let (c1, c2, c3) = (residues::<P1>(a, b), residues::<P2>(a, b), residues::<P3>(a, b));
+    // Garner with the inverses fixed up front
+    let inv_p1_mod_p2 = Mod::<P2>::new(inv_mod(P1 as u64, P2 as u64) as usize);
+    let inv_p1p2_mod_p3 = Mod::<P3>::new(inv_mod((P1 * P2 % P3) as u64, P3 as u64) as usize);
+    let m = modulus as u128;
+    let p1p2_mod_m = (P1 as u128 * P2 as u128 % m) as u64;
+    c1.iter().zip(&c2).zip(&c3).map(|((x1, x2), x3)| {
+        let t1 = x1.value;
+        let t2 = ((*x2 - Mod::new(t1)) * inv_p1_mod_p2).value;
+        let t3 = ((*x3 - Mod::new(t1) - Mod::new(t2) * Mod::new(P1)) * inv_p1p2_mod_p3).value;
+        let value = (t1 as u128 + t2 as u128 * P1 as u128) % m + t3 as u128 * p1p2_mod_m as u128;
+        (value % m) as u64
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    const P: usize = 998_244_353;
+
+    fn naive(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+        if a.is_empty() || b.is_empty() { return Vec::new(); }
+        let mut result = vec![0u128; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                result[i + j] = (result[i + j] + x as u128 * y as u128) % m as u128;
+            }
+        }
+        result.into_iter().map(|x| x as u64).collect()
+    }
+
+    #[test]
+    fn test_convolution_against_naive() {
+        let mut rng = XorShift64::new(15);
+        for (n, m) in [(0, 5), (1, 1), (3, 100), (100, 37), (200, 300)] {
+            let a: Vec<u64> = (0..n).map(|_| rng.gen_range(0..P) as u64).collect();
+            let b: Vec<u64> = (0..m).map(|_| rng.gen_range(0..P) as u64).collect();
+            let fa: Vec<Mod<P>> = a.iter().map(|&x| Mod::from(x)).collect();
+            let fb: Vec<Mod<P>> = b.iter().map(|&x| Mod::from(x)).collect();
+            let got: Vec<u64> = convolution(&fa, &fb).iter().map(|x| x.value as u64).collect();
+            assert_eq!(got, naive(&a, &b, P as u64));
+        }
+        assert_eq!(Root::<P>::G, 3);
+    }
+
+    #[test]
+    fn test_convolution_mod() {
+        let mut rng = XorShift64::new(16);
+        for modulus in [1_000_000_007, 2, 1 << 30] {
+            let a: Vec<u64> = (0..150).map(|_| rng.next_u64() % modulus).collect();
+            let b: Vec<u64> = (0..90).map(|_| rng.next_u64() % modulus).collect();
+            assert_eq!(convolution_mod(&a, &b, modulus), naive(&a, &b, modulus));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,378 @@
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Stein's algorithm: shifts and subtractions only.
+pub fn binary_gcd(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 || b == 0 { return a | b; }
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    while b != 0 {
+        b >>= b.trailing_zeros();
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b -= a;
+    }
+    a << shift
+}
+
+/// `None` when the result does not fit in `u64`; `lcm(0, x) == 0`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 { return Some(0); }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// `(g, x, y)` with `a x + b y == g == gcd(a, b)` and `g >= 0`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+/// `x` with `a * x % m == 1`; `a` and `m` must be coprime.
+pub fn inv_mod(a: u64, m: u64) -> u64 {
+    let (g, x, _) = ext_gcd(a as i128, m as i128);
+    assert!(g == 1, "{} has no inverse modulo {}", a, m);
+    x.rem_euclid(m as i128) as u64
+}
+
+pub const fn pow_mod(mut base: u64, mut nth: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while nth > 0 {
+        if nth % 2 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        nth /= 2;
+    }
+    result
+}
+
+const fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// Deterministic Miller–Rabin for all `u64`.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 { return false; }
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n % p == 0 { return n == p; }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    // these bases decide every n < 2^64
+    [2, 325, 9375, 28178, 450775, 9780504, 1795265022].iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 0 || x == 1 || x == n - 1 { return true; }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 { return true; }
+        }
+        false
+    })
+}
+
+/// Some nontrivial factor of an odd composite `n`, by Pollard's rho with Brent's cycle finding.
+fn pollard_rho(n: u64) -> u64 {
+    const BATCH: usize = 128;
+    for c in 1u128.. {
+        let f = |x: u64| ((mul_mod(x, x, n) as u128 + c) % n as u128) as u64;
+        let (mut x, mut y, mut ys) = (0, 2, 2);
+        let (mut g, mut q) = (1, 1);
+        let mut r = 1;
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+        if g == n {
+            // the batch overshot; redo it one step at a time
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g != 1 { break; }
+            }
+        }
+        if g != n { return g; }
+    }
+    unreachable!()
+}
+
+/// `(prime, exponent)` pairs in increasing order of prime, for any positive `u64`.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    assert!(n >= 1, "cannot factorize 0");
+    let mut primes = Vec::new();
+    let mut stack = vec![n];
+    while let Some(mut m) = stack.pop() {
+        for p in [2, 3, 5, 7] {
+            while m % p == 0 {
+                primes.push(p);
+                m /= p;
+            }
+        }
+        if m == 1 { continue; }
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+    primes.sort_unstable();
+    let mut result: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match result.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => result.push((p, 1)),
+        }
+    }
+    result
+}
+
+/// Combines `x ≡ r (mod m)` for every `(r, m)` into `(x, lcm)`, with possibly non-coprime
+/// moduli; `None` if the congruences contradict. Panics if the lcm overflows `u64`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut r0, mut m0) = (0u64, 1u64);
+    for &(r, m) in congruences {
+        let r = r % m;
+        let g = gcd(m0, m);
+        let diff = (r as i128 - r0 as i128).rem_euclid(m as i128) as u64;
+        if diff % g != 0 { return None; }
+        let (step, m_g) = (m0 / g, m / g);
+        // k with r0 + m0 k ≡ r (mod m), reduced modulo m / g
+        let k = (diff / g) as u128 * inv_mod(step % m_g, m_g) as u128 % m_g as u128;
+        let lcm = step.checked_mul(m).expect("lcm overflows u64");
+        r0 = ((r0 as u128 + m0 as u128 * k) % lcm as u128) as u64;
+        m0 = lcm;
+    }
+    Some((r0, m0))
+}
+
+/// Sum of `floor((a * i + b) / m)` over `0 <= i < n`, in O(log m).
+pub fn floor_sum(n: u64, m: u64, a: i64, b: i64) -> i128 {
+    assert!(m > 0, "m must be positive");
+    let (n, m) = (n as i128, m as i128);
+    let (a, b) = (a as i128, b as i128);
+    // move the negative parts of a and b out of the floor
+    let mut result = a.div_euclid(m) * (n * (n - 1) / 2) + b.div_euclid(m) * n;
+    let (mut n, mut m) = (n as u128, m as u128);
+    let (mut a, mut b) = (a.rem_euclid(m as i128) as u128, b.rem_euclid(m as i128) as u128);
+    loop {
+        if a >= m {
+            result += (n * n.saturating_sub(1) / 2 * (a / m)) as i128;
+            a %= m;
+        }
+        if b >= m {
+            result += (n * (b / m)) as i128;
+            b %= m;
+        }
+        let y_max = a * n + b;
+        if y_max < m { return result; }
+        (n, b) = (y_max / m, y_max % m);
+        (m, a) = (a, m);
+    }
+}
+
+/// Blocks `(begin, end, q)` with `n / i == q` for every `i` in `begin..end`, covering
+/// `1..=n` in O(sqrt n) blocks.
+pub fn floor_blocks(n: u64) -> impl Iterator<Item = (u64, u64, u64)> {
+    let mut i = 1;
+    std::iter::from_fn(move || {
+        if i > n { return None; }
+        let q = n / i;
+        let end = n / q + 1;
+        let block = (i, end, q);
+        i = end;
+        Some(block)
+    })
+}
+
+/// Digits `t` with `x = t[0] + t[1] m[0] + t[2] m[0] m[1] + ...`, each `t[i] < m[i]`.
+fn mixed_radix(residues: &[u64], moduli: &[u64]) -> Vec<u64> {
+    assert_eq!(residues.len(), moduli.len());
+    let mut digits: Vec<u64> = Vec::with_capacity(moduli.len());
+    for (i, (&r, &m)) in residues.iter().zip(moduli).enumerate() {
+        let m = m as u128;
+        // value of the digits so far, and the product of the earlier moduli, modulo m
+        let (mut value, mut radix) = (0u128, 1u128);
+        for (&t, &prev) in digits.iter().zip(&moduli[..i]) {
+            value = (value + t as u128 * radix) % m;
+            radix = radix * prev as u128 % m;
+        }
+        let diff = (r as u128 % m + m - value) % m;
+        digits.push((diff * inv_mod(radix as u64, m as u64) as u128 % m) as u64);
+    }
+    digits
+}
+
+/// Garner's algorithm: the `x` with `x % moduli[i] == residues[i]` for pairwise coprime
+/// moduli, reduced modulo an arbitrary `modulus`.
+pub fn garner(residues: &[u64], moduli: &[u64], modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let (mut result, mut radix) = (0u128, 1u128 % modulus);
+    for (t, &m) in mixed_radix(residues, moduli).into_iter().zip(moduli) {
+        result = (result + t as u128 % modulus * radix) % modulus;
+        radix = radix * (m as u128 % modulus) % modulus;
+    }
+    result as u64
+}
+
+/// Exact Chinese remaindering; panics if the answer could exceed `u128`.
+pub fn crt_u128(residues: &[u64], moduli: &[u64]) -> u128 {
+    let (mut result, mut radix) = (0u128, 1u128);
+    for (t, &m) in mixed_radix(residues, moduli).into_iter().zip(moduli) {
+        result = radix.checked_mul(t as u128)
+            .and_then(|x| x.checked_add(result))
+            .expect("overflow in crt_u128");
+        radix = radix.saturating_mul(m as u128);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        let mut rng = crate::rng::XorShift64::new(7);
+        for _ in 0..1000 {
+            let a = rng.next_u64() >> rng.gen_range(0..64);
+            let b = rng.next_u64() >> rng.gen_range(0..64);
+            assert_eq!(binary_gcd(a, b), gcd(a, b));
+            let (g, x, y) = ext_gcd(a as i128, -(b as i128));
+            assert_eq!(g, gcd(a, b) as i128);
+            assert_eq!(a as i128 * x - b as i128 * y, g);
+        }
+        assert_eq!(binary_gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(1 << 40, 3 << 39), Some(3 << 40));
+        assert_eq!(lcm(1 << 40, 3 << 30), Some(3 << 40));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(inv_mod(3, 10), 7);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let sieve = crate::sieve::Sieve::new(10_000);
+        for n in 0..=10_000 {
+            assert_eq!(is_prime(n as u64), sieve.is_prime(n), "{}", n);
+        }
+        assert!(is_prime(998_244_353));
+        assert!(is_prime((1 << 61) - 1));
+        assert!(is_prime(18_446_744_073_709_551_557)); // largest prime below 2^64
+        assert!(!is_prime(3_215_031_751)); // strong pseudoprime to bases 2, 3, 5, 7
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert!(factorize(1).is_empty());
+        assert_eq!(factorize(360), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1_000_000_007 * 998_244_353), [(998_244_353, 1), (1_000_000_007, 1)]);
+        assert_eq!(factorize(999_999_999_999_999_989), [(999_999_999_999_999_989, 1)]);
+        assert_eq!(factorize(1 << 63), [(2, 63)]);
+        assert_eq!(factorize(4_295_098_369), [(65_537, 2)]);
+        let mut rng = crate::rng::XorShift64::new(9);
+        for _ in 0..200 {
+            let n = rng.next_u64() >> rng.gen_range(0..40) | 1;
+            let factors = factorize(n);
+            assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+            assert_eq!(factors.iter().fold(1u64, |acc, &(p, e)| acc * p.pow(e)), n);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(7, 10), (7, 10)]), Some((7, 10)));
+        assert_eq!(crt(&[(5, 1), (3, 8)]), Some((3, 8)));
+        let big = (1u64 << 61) - 1;
+        assert_eq!(crt(&[(big - 1, big), (1, 2)]), Some((2 * big - 1, 2 * big)));
+
+        let mut rng = crate::rng::XorShift64::new(8);
+        for _ in 0..500 {
+            let x = rng.gen_range(0..10_000) as u64;
+            let congruences: Vec<_> = (0..3).map(|_| {
+                let m = rng.gen_range(1..60) as u64;
+                (x % m, m)
+            }).collect();
+            let (r, l) = crt(&congruences).unwrap();
+            assert_eq!(l, congruences.iter().fold(1, |acc, &(_, m)| lcm(acc, m).unwrap()));
+            assert_eq!(r, x % l);
+        }
+    }
+
+    #[test]
+    fn test_floor_sum() {
+        let mut rng = crate::rng::XorShift64::new(10);
+        for _ in 0..2000 {
+            let n = rng.gen_range(0..50) as u64;
+            let m = rng.gen_range(1..30) as u64;
+            let a = rng.gen_range(0..200) as i64 - 100;
+            let b = rng.gen_range(0..200) as i64 - 100;
+            let expected: i128 = (0..n as i64).map(|i| (a * i + b).div_euclid(m as i64) as i128).sum();
+            assert_eq!(floor_sum(n, m, a, b), expected);
+        }
+        assert_eq!(floor_sum(1 << 32, 1 << 32, 1 << 32, 0), (1i128 << 32) * ((1 << 32) - 1) / 2);
+    }
+
+    #[test]
+    fn test_floor_blocks() {
+        for n in 0..200 {
+            let mut covered = 1;
+            for (begin, end, q) in floor_blocks(n) {
+                assert_eq!(begin, covered);
+                assert!(begin < end);
+                assert!((begin..end).all(|i| n / i == q));
+                covered = end;
+            }
+            assert_eq!(covered, n + 1);
+        }
+        assert_eq!(floor_blocks(10).collect::<Vec<_>>(), [(1, 2, 10), (2, 3, 5), (3, 4, 3), (4, 6, 2), (6, 11, 1)]);
+    }
+
+    #[test]
+    fn test_garner() {
+        const MODULI: [u64; 3] = [167_772_161, 469_762_049, 754_974_721];
+        let x: u128 = 123_456_789_012_345_678_901_234;
+        let residues: Vec<u64> = MODULI.iter().map(|&m| (x % m as u128) as u64).collect();
+        assert_eq!(crt_u128(&residues, &MODULI), x);
+        assert_eq!(garner(&residues, &MODULI, 1_000_000_007), (x % 1_000_000_007) as u64);
+        assert_eq!(garner(&residues, &MODULI, 1), 0);
+
+        // non-prime moduli
+        assert_eq!(crt_u128(&[2, 3, 1], &[3, 4, 5]), 11);
+        assert_eq!(garner(&[2, 3, 1], &[3, 4, 5], 7), 4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A hash map iterating in the order keys were first inserted.
+#[derive(Clone)]
+pub struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+    index: HashMap<K, usize>,
+}
+
+impl<K, V> OrderedMap<K, V> where
+    K: Hash + Eq + Clone {
+    pub fn new() -> Self {
+        Self { entries: Vec::new(), index: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the previous value; an existing key keeps its original position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&i) = self.index.get(&key) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+        let i = match self.index.get(&key) {
+            Some(&i) => i,
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, default()));
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[i].1
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.index.get(key).map(|&i| &mut self.entries[i].1)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Position of `key` in insertion order.
+    pub fn position(&self, key: &K) -> Option<usize> {
+        self.index.get(key).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> where
+    K: Hash + Eq + Clone {
+    fn default() -> Self { Self::new() }
+}
+
+impl<K, V> FromIterator<(K, V)> for OrderedMap<K, V> where
+    K: Hash + Eq + Clone {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for (key, value) in iter {
+            result.insert(key, value);
+        }
+        result
+    }
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+    fn into_iter(self) -> Self::IntoIter { self.entries.into_iter() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderedMap;
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map = OrderedMap::new();
+        for word in "the quick brown fox jumps over the lazy dog the end".split(' ') {
+            *map.get_or_insert_with(word, || 0) += 1;
+        }
+        let words: Vec<_> = map.keys().copied().collect();
+        assert_eq!(words, ["the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog", "end"]);
+        assert_eq!(map.get(&"the"), Some(&3));
+        assert_eq!(map.position(&"dog"), Some(7));
+
+        assert_eq!(map.insert("quick", 10), Some(1));
+        assert_eq!(map.insert("cat", 1), None);
+        let entries: Vec<_> = map.into_iter().skip(1).take(2).collect();
+        assert_eq!(entries, [("quick", 10), ("brown", 1)]);
+    }
+}
\ No newline at end of file
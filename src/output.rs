@@ -0,0 +1,77 @@
+use std::fmt::Display;
+use std::io::{self, BufWriter, Write};
+
+use crate::grid::Grid;
+
+/// One line per row, with no separator between cells.
+pub fn format_grid(grid: &Grid<char>) -> String {
+    let (height, width) = grid.size();
+    let mut result = String::with_capacity(height * (width + 1));
+    for i in 0..height {
+        result.extend(grid.row(i));
+        result.push('\n');
+    }
+    result
+}
+
+pub fn write_matrix<T>(out: &mut impl Write, matrix: &[Vec<T>], sep: &str) -> io::Result<()> where
+    T: Display {
+    for row in matrix {
+        for (j, value) in row.iter().enumerate() {
+            if j > 0 {
+                out.write_all(sep.as_bytes())?;
+            }
+            write!(out, "{}", value)?;
+        }
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Writes `matrix` to stdout through a single buffer.
+pub fn print_matrix<T>(matrix: &[Vec<T>], sep: &str) where
+    T: Display {
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_matrix(&mut out, matrix, sep).unwrap();
+    out.flush().unwrap();
+}
+
+/// Fixed-point with exactly `digits` decimals, never scientific notation, and without
+/// the sign on values that round to zero.
+pub fn format_float(x: f64, digits: usize) -> String {
+    assert!(x.is_finite(), "cannot format {}", x);
+    let result = format!("{:.*}", digits, x);
+    match result.strip_prefix('-') {
+        Some(rest) if rest.bytes().all(|b| b == b'0' || b == b'.') => rest.to_string(),
+        _ => result,
+    }
+}
+
+pub fn print_float(x: f64, digits: usize) {
+    println!("{}", format_float(x, digits));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let grid: Grid<char> = vec!["#.".chars().collect(), ".#".chars().collect()].into_iter().collect();
+        assert_eq!(format_grid(&grid), "#.\n.#\n");
+
+        let mut out = Vec::new();
+        write_matrix(&mut out, &[vec![1, 2, 3], vec![4, 5, 6]], " ").unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "1 2 3\n4 5 6\n");
+    }
+
+    #[test]
+    fn test_format_float() {
+        assert_eq!(format_float(1.0 / 3.0, 10), "0.3333333333");
+        assert_eq!(format_float(1e-12, 3), "0.000");
+        assert_eq!(format_float(-1e-12, 3), "0.000");
+        assert_eq!(format_float(-0.5, 1), "-0.5");
+        assert_eq!(format_float(1e20, 2), "100000000000000000000.00");
+        assert_eq!(format_float(2.5, 0), "2");
+    }
+}
\ No newline at end of file
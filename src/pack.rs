@@ -0,0 +1,107 @@
+/// `u * width + v`, the flat index of `(u, v)` in a row-major table.
+pub fn pack(u: usize, v: usize, width: usize) -> usize {
+    debug_assert!(v < width, "{} does not fit in width {}", v, width);
+    u * width + v
+}
+
+pub fn unpack(x: usize, width: usize) -> (usize, usize) {
+    (x / width, x % width)
+}
+
+/// Row-major numbering of the tuples in `[0, dims[0]) × ... × [0, dims[D - 1])`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Shape<const D: usize> {
+    dims: [usize; D],
+}
+
+impl<const D: usize> Shape<D> {
+    pub fn new(dims: [usize; D]) -> Self {
+        Self { dims }
+    }
+
+    pub fn len(&self) -> usize {
+        self.dims.iter().product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn pack(&self, state: [usize; D]) -> usize {
+        state.iter().zip(&self.dims).fold(0, |acc, (&x, &dim)| pack(acc, x, dim))
+    }
+
+    pub fn unpack(&self, mut x: usize) -> [usize; D] {
+        let mut state = [0; D];
+        for (s, &dim) in state.iter_mut().zip(&self.dims).rev() {
+            (x, *s) = unpack(x, dim);
+        }
+        state
+    }
+}
+
+/// Set of states of a fixed `Shape`, stored as a flat `Vec<bool>` instead of a hash set.
+#[derive(Clone)]
+pub struct DenseSet<const D: usize> {
+    shape: Shape<D>,
+    present: Vec<bool>,
+}
+
+impl<const D: usize> DenseSet<D> {
+    pub fn new(dims: [usize; D]) -> Self {
+        let shape = Shape::new(dims);
+        let present = vec![false; shape.len()];
+        Self { shape, present }
+    }
+
+    /// Returns whether `state` was newly added.
+    pub fn insert(&mut self, state: [usize; D]) -> bool {
+        let x = self.shape.pack(state);
+        !std::mem::replace(&mut self.present[x], true)
+    }
+
+    pub fn remove(&mut self, state: [usize; D]) -> bool {
+        let x = self.shape.pack(state);
+        std::mem::replace(&mut self.present[x], false)
+    }
+
+    pub fn contains(&self, state: [usize; D]) -> bool {
+        self.present[self.shape.pack(state)]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = [usize; D]> + '_ {
+        self.present.iter().enumerate().filter(|(_, &p)| p).map(|(x, _)| self.shape.unpack(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_roundtrip() {
+        assert_eq!(pack(2, 3, 5), 13);
+        assert_eq!(unpack(13, 5), (2, 3));
+
+        let shape = Shape::new([3, 4, 2]);
+        assert_eq!(shape.len(), 24);
+        let packed: Vec<_> = (0..3)
+            .flat_map(|r| (0..4).flat_map(move |c| (0..2).map(move |d| shape.pack([r, c, d]))))
+            .collect();
+        assert_eq!(packed, (0..24).collect::<Vec<_>>());
+        assert_eq!(shape.unpack(shape.pack([2, 1, 1])), [2, 1, 1]);
+    }
+
+    #[test]
+    fn test_dense_set() {
+        let mut visited = DenseSet::new([3, 4, 4]);
+        assert!(visited.insert([1, 2, 3]));
+        assert!(!visited.insert([1, 2, 3]));
+        assert!(visited.insert([0, 0, 1]));
+        assert!(visited.contains([1, 2, 3]));
+        assert!(!visited.contains([1, 3, 2]));
+        assert_eq!(visited.iter().collect::<Vec<_>>(), [[0, 0, 1], [1, 2, 3]]);
+        assert!(visited.remove([0, 0, 1]));
+        assert!(!visited.remove([0, 0, 1]));
+    }
+}
\ No newline at end of file
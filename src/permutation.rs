@@ -0,0 +1,57 @@
+/// `q` with `q[p[i]] == i`.
+pub fn inverse_permutation(p: &[usize]) -> Vec<usize> {
+    let mut result = vec![0; p.len()];
+    for (i, &x) in p.iter().enumerate() {
+        result[x] = i;
+    }
+    result
+}
+
+/// Rearranges `a` in place so that the new `a[i]` is the old `a[perm[i]]`, following cycles.
+/// With `perm = argsort(a)` this sorts `a`.
+pub fn apply_permutation<T>(perm: &[usize], a: &mut [T]) {
+    assert_eq!(perm.len(), a.len(), "permutation and slice lengths differ");
+    let mut done = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        if done[start] { continue; }
+        let mut j = start;
+        done[j] = true;
+        while perm[j] != start {
+            a.swap(j, perm[j]);
+            j = perm[j];
+            done[j] = true;
+        }
+    }
+}
+
+/// `r[i] = p[q[i]]`: applying `p` and then `q` with `apply_permutation` is applying `r`.
+pub fn compose(p: &[usize], q: &[usize]) -> Vec<usize> {
+    q.iter().map(|&i| p[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::argsort::argsort;
+
+    #[test]
+    fn test_permutations() {
+        let p = [2, 0, 3, 1];
+        let q = [1, 3, 0, 2];
+        assert_eq!(inverse_permutation(&p), [1, 3, 0, 2]);
+        assert_eq!(compose(&p, &inverse_permutation(&p)), [0, 1, 2, 3]);
+
+        let mut a = ['a', 'b', 'c', 'd'];
+        apply_permutation(&p, &mut a);
+        assert_eq!(a, ['c', 'a', 'd', 'b']);
+        apply_permutation(&q, &mut a);
+        let mut b = ['a', 'b', 'c', 'd'];
+        apply_permutation(&compose(&p, &q), &mut b);
+        assert_eq!(a, b);
+
+        let mut words = vec!["pear", "apple", "fig", "kiwi"];
+        let order = argsort(&words);
+        apply_permutation(&order, &mut words);
+        assert_eq!(words, ["apple", "fig", "kiwi", "pear"]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::ops::RangeBounds;
+
+use crate::monoid::Monoid;
+use crate::range::to_half_open;
+
+/// Segment tree with point assignment and range `combine`, without lazy propagation.
+#[derive(Clone)]
+pub struct PointSegmentTree<T> where
+    T: Monoid {
+    size: usize,
+    offset: usize,
+    values: Vec<T>, // 1-indexed, leaf i at offset + i
+}
+
+impl<T> PointSegmentTree<T> where
+    T: Monoid {
+    pub fn new(size: usize) -> Self {
+        let offset = size.next_power_of_two();
+        let values = vec![T::ident(); 2 * offset];
+        Self { size, offset, values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn set(&mut self, i: usize, value: T) {
+        assert!(i < self.size, "index {} out of range for size {}", i, self.size);
+        let mut idx = self.offset + i;
+        self.values[idx] = value;
+        while idx > 1 {
+            idx /= 2;
+            self.update_node(idx);
+        }
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        assert!(i < self.size, "index {} out of range for size {}", i, self.size);
+        self.values[self.offset + i]
+    }
+
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.size);
+        let mut left = T::ident();
+        let mut right = T::ident();
+        let (mut lo, mut hi) = (begin + self.offset, end + self.offset);
+        while lo < hi {
+            if lo & 1 == 1 {
+                left = left.combine(self.values[lo]);
+                lo += 1;
+            }
+            if hi & 1 == 1 {
+                hi -= 1;
+                right = self.values[hi].combine(right);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        left.combine(right)
+    }
+
+    fn update_node(&mut self, idx: usize) {
+        self.values[idx] = self.values[2 * idx].combine(self.values[2 * idx + 1]);
+    }
+}
+
+impl<T> FromIterator<T> for PointSegmentTree<T> where
+    T: Monoid {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let leaves: Vec<T> = iter.into_iter().collect();
+        let mut result = Self::new(leaves.len());
+        let offset = result.offset;
+        result.values[offset..offset + leaves.len()].copy_from_slice(&leaves);
+        for idx in (1..offset).rev() {
+            result.update_node(idx);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modint::Mod;
+    use crate::segment_tree::Affine;
+
+    #[test]
+    fn test_set_and_query() {
+        let mut segtree: PointSegmentTree<usize> = [3, 1, 4, 1, 5].into_iter().collect();
+        assert_eq!(segtree.query(..), 5);
+        assert_eq!(segtree.query(0..3), 4);
+
+        segtree.set(2, 0);
+        assert_eq!(segtree.query(0..3), 3);
+        assert_eq!(segtree.get(2), 0);
+        assert_eq!(segtree.query(3..3), 0);
+    }
+
+    #[test]
+    fn test_point_set_range_composite() {
+        type M = Mod<998_244_353>;
+        let f = |a, b| Affine::new(M::new(a), M::new(b));
+        let mut segtree: PointSegmentTree<Affine<M>> = vec![f(2, 1), f(3, 4), f(1, 5)].into_iter().collect();
+
+        // f2(f1(f0(1))) = (3 * (2 * 1 + 1) + 4) + 5
+        assert_eq!(segtree.query(..).eval(M::new(1)).value, 18);
+        segtree.set(1, f(1, 0));
+        // f2(f0(1)) = 3 + 5
+        assert_eq!(segtree.query(..).eval(M::new(1)).value, 8);
+        assert_eq!(segtree.query(1..).eval(M::new(7)).value, 12);
+    }
+}
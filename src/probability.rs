@@ -0,0 +1,72 @@
+use crate::modint::Mod;
+
+/// The probability `p / q` encoded as `p * q^{-1}` modulo the prime `P`.
+pub fn prob<const P: usize>(p: usize, q: usize) -> Mod<P> {
+    Mod::new(p) / Mod::new(q)
+}
+
+/// Expected value of a distribution given as `(probability, value)` pairs.
+pub fn expectation<const P: usize>(outcomes: impl IntoIterator<Item = (Mod<P>, Mod<P>)>) -> Mod<P> {
+    outcomes.into_iter().fold(Mod::new(0), |acc, (p, value)| acc + p * value)
+}
+
+/// Solves `a x = b` over `Mod<P>` by Gaussian elimination, returning `None` unless the
+/// solution is unique. `P` must be prime. O(n^3).
+pub fn solve_linear<const P: usize>(mut a: Vec<Vec<Mod<P>>>, mut b: Vec<Mod<P>>) -> Option<Vec<Mod<P>>> {
+    let n = b.len();
+    assert!(a.len() == n && a.iter().all(|row| row.len() == n), "expected a square system");
+    for col in 0..n {
+        let pivot = (col..n).find(|&row| a[row][col].value != 0)?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let inv = a[col][col].inv();
+        for x in &mut a[col][col..] {
+            *x *= inv;
+        }
+        b[col] *= inv;
+        let pivot_row = a[col].clone();
+        let pivot_b = b[col];
+        for (row, (lhs, rhs)) in a.iter_mut().zip(b.iter_mut()).enumerate() {
+            let factor = lhs[col];
+            if row == col || factor.value == 0 { continue; }
+            for (x, &y) in lhs[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * y;
+            }
+            *rhs -= factor * pivot_b;
+        }
+    }
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: usize = 998_244_353;
+    type M = Mod<P>;
+
+    #[test]
+    fn test_dice_expectation() {
+        let die = (1..=6).map(|face| (prob::<P>(1, 6), M::new(face)));
+        assert_eq!(expectation(die).value, prob::<P>(7, 2).value);
+    }
+
+    #[test]
+    fn test_absorbing_random_walk() {
+        // Walk on 0..=4 moving left or right with probability 1/2, absorbed at 0 and 4.
+        // Expected steps E[i] = i * (4 - i), so E = [3, 4, 3] for the transient states 1..=3.
+        let half = prob::<P>(1, 2);
+        let n = 3;
+        let mut a = vec![vec![M::new(0); n]; n];
+        for i in 0..n {
+            a[i][i] = M::new(1);
+            if i > 0 { a[i][i - 1] = -half; }
+            if i + 1 < n { a[i][i + 1] = -half; }
+        }
+        let steps = solve_linear(a, vec![M::new(1); n]).unwrap();
+        assert_eq!(steps.iter().map(|x| x.value).collect::<Vec<_>>(), [3, 4, 3]);
+
+        let singular = vec![vec![M::new(1), M::new(2)], vec![M::new(2), M::new(4)]];
+        assert!(solve_linear(singular, vec![M::new(1), M::new(2)]).is_none());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+use crate::disjoint_set::RollbackDisjointSet;
+
+/// A structure whose operations can be undone in reverse order.
+pub trait Rollback {
+    type Op: Clone;
+    fn apply(&mut self, op: Self::Op);
+    /// Reverts the latest applied operation.
+    fn rollback(&mut self);
+}
+
+impl Rollback for RollbackDisjointSet {
+    type Op = (usize, usize);
+    fn apply(&mut self, (u, v): Self::Op) { self.union(u, v); }
+    fn rollback(&mut self) { self.undo() }
+}
+
+/// Lets a `Rollback` structure drop its oldest operation instead of its latest,
+/// at an amortized O(log n) extra applications per operation.
+#[derive(Clone)]
+pub struct QueueUndo<S> where
+    S: Rollback {
+    inner: S,
+    stack: Vec<(S::Op, bool)>, // true for ops already reordered towards the front
+    front_len: usize,
+}
+
+impl<S> QueueUndo<S> where
+    S: Rollback {
+    pub fn new(inner: S) -> Self {
+        Self { inner, stack: Vec::new(), front_len: 0 }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    pub fn push(&mut self, op: S::Op) {
+        self.inner.apply(op.clone());
+        self.stack.push((op, false));
+    }
+
+    /// Removes the oldest operation still applied.
+    pub fn pop(&mut self) {
+        assert!(!self.stack.is_empty(), "no operation to pop");
+        if self.front_len == 0 {
+            // Reverse everything so the oldest operation ends up on top.
+            let popped: Vec<S::Op> = (0..self.stack.len()).map(|_| self.pop_top().0).collect();
+            for op in popped {
+                self.push_top(op, true);
+            }
+        } else if !self.stack.last().unwrap().1 {
+            let mut fronts = Vec::new();
+            let mut backs = Vec::new();
+            // Dig until as many front ops as back ops are lifted, or none are left below.
+            loop {
+                let (op, is_front) = self.pop_top();
+                if is_front { fronts.push(op); } else { backs.push(op); }
+                if self.front_len == 0 || fronts.len() == backs.len() { break; }
+            }
+            for op in backs.into_iter().rev() {
+                self.push_top(op, false);
+            }
+            for op in fronts.into_iter().rev() {
+                self.push_top(op, true);
+            }
+        }
+        self.pop_top();
+    }
+
+    fn push_top(&mut self, op: S::Op, is_front: bool) {
+        self.inner.apply(op.clone());
+        self.stack.push((op, is_front));
+        if is_front {
+            self.front_len += 1;
+        }
+    }
+
+    fn pop_top(&mut self) -> (S::Op, bool) {
+        self.inner.rollback();
+        let (op, is_front) = self.stack.pop().unwrap();
+        if is_front {
+            self.front_len -= 1;
+        }
+        (op, is_front)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_sliding_window_connectivity() {
+        let n = 8;
+        let mut rng = XorShift64::new(3);
+        let edges: Vec<(usize, usize)> = (0..300).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n))).collect();
+        let mut window = QueueUndo::new(RollbackDisjointSet::new(n));
+        let mut begin = 0;
+        for (end, &edge) in edges.iter().enumerate() {
+            window.push(edge);
+            while window.len() > 1 + end % 7 {
+                window.pop();
+                begin += 1;
+            }
+            let mut naive = RollbackDisjointSet::new(n);
+            for &(u, v) in &edges[begin..=end] {
+                naive.union(u, v);
+            }
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(window.inner().same(u, v), naive.same(u, v));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+/// Stable LSD radix sort on a `u64` key, one byte per pass. Passes over bytes that
+/// are equal in every key are skipped, so small keys take fewer passes.
+pub fn radix_sort_by_key<T>(a: &mut [T], key: impl Fn(&T) -> u64) where
+    T: Copy {
+    let n = a.len();
+    let mut counts = [[0; 256]; 8];
+    for x in a.iter() {
+        let k = key(x);
+        for (byte, count) in counts.iter_mut().enumerate() {
+            count[(k >> (8 * byte) & 0xff) as usize] += 1;
+        }
+    }
+    let mut buffer = a.to_vec();
+    let mut in_buffer = false;
+    for (byte, count) in counts.iter_mut().enumerate() {
+        if count.contains(&n) { continue; }
+        let mut offset = 0;
+        for c in count.iter_mut() {
+            (*c, offset) = (offset, offset + *c);
+        }
+        let (from, to): (&[T], &mut [T]) = if in_buffer { (&buffer, a) } else { (a, &mut buffer) };
+        for x in from {
+            let digit = (key(x) >> (8 * byte) & 0xff) as usize;
+            to[count[digit]] = *x;
+            count[digit] += 1;
+        }
+        in_buffer = !in_buffer;
+    }
+    if in_buffer {
+        a.copy_from_slice(&buffer);
+    }
+}
+
+pub fn radix_sort<T>(a: &mut [T]) where
+    T: Copy + Into<u64> {
+    radix_sort_by_key(a, |&x| x.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_against_sort() {
+        let mut rng = XorShift64::new(23);
+        let mut a: Vec<u64> = (0..1000).map(|_| rng.next_u64() >> rng.gen_range(0..64)).collect();
+        let mut expected = a.clone();
+        expected.sort_unstable();
+        radix_sort(&mut a);
+        assert_eq!(a, expected);
+
+        let mut b: Vec<u32> = vec![5, 0, u32::MAX, 3, 3];
+        radix_sort(&mut b);
+        assert_eq!(b, [0, 3, 3, 5, u32::MAX]);
+    }
+
+    #[test]
+    fn test_stable_by_key() {
+        let mut pairs = [(3, 'a'), (1, 'b'), (3, 'c'), (0, 'd'), (1, 'e')];
+        radix_sort_by_key(&mut pairs, |&(k, _)| k);
+        assert_eq!(pairs, [(0, 'd'), (1, 'b'), (1, 'e'), (3, 'a'), (3, 'c')]);
+    }
+}
\ No newline at end of file
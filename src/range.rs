@@ -0,0 +1,60 @@
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RangeError {
+    /// `begin > end`.
+    Reversed { begin: usize, end: usize },
+    /// `end > len`.
+    OutOfBounds { end: usize, len: usize },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::Reversed { begin, end } => write!(f, "range start {} is after its end {}", begin, end),
+            RangeError::OutOfBounds { end, len } => write!(f, "range end {} out of bounds for length {}", end, len),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Resolves `range` into `(begin, end)` with `end` exclusive; unbounded ends map to `0` and `len`.
+/// Panics on reversed or out-of-bounds ranges.
+pub fn to_half_open(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    try_to_half_open(range, len).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Like `to_half_open`, but returns the error instead; an end past `usize::MAX` is out of bounds.
+pub fn try_to_half_open(range: impl RangeBounds<usize>, len: usize) -> Result<(usize, usize), RangeError> {
+    let overflow = RangeError::OutOfBounds { end: usize::MAX, len };
+    let begin = match range.start_bound() {
+        Bound::Included(&begin) => begin,
+        Bound::Excluded(&begin) => begin.checked_add(1).ok_or(overflow)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).ok_or(overflow)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if begin > end { return Err(RangeError::Reversed { begin, end }); }
+    if end > len { return Err(RangeError::OutOfBounds { end, len }); }
+    Ok((begin, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_to_half_open_overflow() {
+        let overflow = Err(RangeError::OutOfBounds { end: usize::MAX, len: 5 });
+        assert_eq!(try_to_half_open(..=usize::MAX, 5), overflow);
+        assert_eq!(try_to_half_open(2..=usize::MAX, 5), overflow);
+        assert_eq!(try_to_half_open((Bound::Excluded(usize::MAX), Bound::Unbounded), 5), overflow);
+        assert_eq!(try_to_half_open(1..=4, 5), Ok((1, 5)));
+        assert_eq!(try_to_half_open((Bound::Excluded(1), Bound::Unbounded), 5), Ok((2, 5)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use crate::event_sweep::EventSweep;
+
+/// Area of the union of axis-aligned rectangles `(x1, y1, x2, y2)` with `x1 <= x2`, `y1 <= y2`.
+/// O(n log n).
+pub fn union_area(rects: &[(i64, i64, i64, i64)]) -> i64 {
+    let mut area = 0;
+    sweep(rects, |prev_x, x, covered_before, _| {
+        area += (x - prev_x) * covered_before;
+    });
+    area
+}
+
+/// Perimeter of the union of axis-aligned rectangles `(x1, y1, x2, y2)`, counting holes' boundaries.
+/// O(n log n).
+pub fn union_perimeter(rects: &[(i64, i64, i64, i64)]) -> i64 {
+    let transposed: Vec<_> = rects.iter().map(|&(x1, y1, x2, y2)| (y1, x1, y2, x2)).collect();
+    let mut perimeter = 0;
+    for rects in [rects, &transposed] {
+        sweep(rects, |_, _, _, boundary| perimeter += boundary);
+    }
+    perimeter
+}
+
+/// Sweeps along x and calls `visit(prev_x, x, covered_before, boundary)` for every distinct
+/// event coordinate, with the covered y-length just before it and the length of union
+/// boundary lying on the line at `x`.
+fn sweep(rects: &[(i64, i64, i64, i64)], mut visit: impl FnMut(i64, i64, i64, i64)) {
+    let mut ys: Vec<i64> = rects.iter().flat_map(|&(_, y1, _, y2)| [y1, y2]).collect();
+    ys.sort_unstable();
+    ys.dedup();
+    if ys.len() < 2 { return; }
+    let mut cover = CoverTree::new(ys.clone());
+    let mut events = EventSweep::new();
+    for &(x1, y1, x2, y2) in rects {
+        if x1 == x2 || y1 == y2 { continue; }
+        let lo = ys.binary_search(&y1).unwrap();
+        let hi = ys.binary_search(&y2).unwrap();
+        events.push(x1, (lo, hi, 1));
+        events.push(x2, (lo, hi, -1));
+    }
+    let mut prev_x = None;
+    events.run(|&x, batch| {
+        let before = cover.covered();
+        // Openings first, so that rectangles touching along this line do not count as boundary.
+        let mut batch = batch.to_vec();
+        batch.sort_unstable_by_key(|&(_, _, delta)| -delta);
+        let mut boundary = 0;
+        for (lo, hi, delta) in batch {
+            let covered = cover.covered();
+            cover.add(lo, hi, delta);
+            boundary += (cover.covered() - covered).abs();
+        }
+        visit(prev_x.unwrap_or(x), x, before, boundary);
+        prev_x = Some(x);
+    });
+}
+
+/// Covered length of the elementary segments `ys[i]..ys[i + 1]` under interval add.
+struct CoverTree {
+    ys: Vec<i64>,
+    counts: Vec<i32>,
+    covered: Vec<i64>,
+}
+
+impl CoverTree {
+    fn new(ys: Vec<i64>) -> Self {
+        let size = 4 * ys.len();
+        Self { ys, counts: vec![0; size], covered: vec![0; size] }
+    }
+
+    fn covered(&self) -> i64 {
+        self.covered[1]
+    }
+
+    fn add(&mut self, lo: usize, hi: usize, delta: i32) {
+        let last = self.ys.len() - 1;
+        self._add(lo, hi, delta, 1, 0, last);
+    }
+
+    fn _add(&mut self, lo: usize, hi: usize, delta: i32, idx: usize, begin: usize, end: usize) {
+        if hi <= begin || end <= lo { return; }
+        if lo <= begin && end <= hi {
+            self.counts[idx] += delta;
+        } else {
+            let mid = (begin + end) / 2;
+            self._add(lo, hi, delta, 2 * idx, begin, mid);
+            self._add(lo, hi, delta, 2 * idx + 1, mid, end);
+        }
+        self.covered[idx] = if self.counts[idx] > 0 {
+            self.ys[end] - self.ys[begin]
+        } else if end - begin == 1 {
+            0
+        } else {
+            self.covered[2 * idx] + self.covered[2 * idx + 1]
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_union() {
+        let rects = [(0, 0, 2, 2), (1, 1, 3, 3)];
+        assert_eq!(union_area(&rects), 7);
+        assert_eq!(union_perimeter(&rects), 12);
+        // Touching squares merge into one 2x1 rectangle.
+        assert_eq!(union_perimeter(&[(0, 0, 1, 1), (1, 0, 2, 1)]), 6);
+        assert_eq!(union_area(&[]), 0);
+    }
+
+    #[test]
+    fn test_random_against_grid() {
+        let mut rng = XorShift64::new(31);
+        for _ in 0..50 {
+            let mut grid = [[false; 10]; 10];
+            let rects: Vec<_> = (0..5)
+                .map(|_| {
+                    let (x1, y1) = (rng.gen_range(0..10), rng.gen_range(0..10));
+                    let (x2, y2) = (rng.gen_range(x1..11), rng.gen_range(y1..11));
+                    for row in &mut grid[x1..x2] {
+                        row[y1..y2].fill(true);
+                    }
+                    (x1 as i64, y1 as i64, x2 as i64, y2 as i64)
+                })
+                .collect();
+            let cell = |x: i64, y: i64| (0..10).contains(&x) && (0..10).contains(&y) && grid[x as usize][y as usize];
+            let mut area = 0;
+            let mut perimeter = 0;
+            for x in -1..=10 {
+                for y in -1..=10 {
+                    area += cell(x, y) as i64;
+                    perimeter += (cell(x, y) != cell(x + 1, y)) as i64 + (cell(x, y) != cell(x, y + 1)) as i64;
+                }
+            }
+            assert_eq!(union_area(&rects), area);
+            assert_eq!(union_perimeter(&rects), perimeter);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use std::collections::BinaryHeap;
+
+/// Max-heap with lazy deletion: removed values are dropped once they reach the top.
+#[derive(Clone)]
+pub struct RemovableHeap<T> where
+    T: Ord {
+    heap: BinaryHeap<T>,
+    removed: BinaryHeap<T>,
+}
+
+impl<T> RemovableHeap<T> where
+    T: Ord {
+    pub fn new() -> Self {
+        Self { heap: BinaryHeap::new(), removed: BinaryHeap::new() }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.heap.push(value);
+    }
+
+    /// `value` must currently be in the heap.
+    pub fn remove(&mut self, value: T) {
+        self.removed.push(value);
+    }
+
+    pub fn peek(&mut self) -> Option<&T> {
+        self.clean();
+        self.heap.peek()
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.clean();
+        self.heap.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len() - self.removed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clean(&mut self) {
+        while let (Some(top), Some(removed)) = (self.heap.peek(), self.removed.peek()) {
+            if top != removed { break; }
+            self.heap.pop();
+            self.removed.pop();
+        }
+    }
+}
+
+impl<T> Default for RemovableHeap<T> where
+    T: Ord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,95 @@
+#[derive(Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        let state = seed ^ 0x9e37_79b9_7f4a_7c15;
+        Self { state: if state == 0 { 1 } else { state } }
+    }
+
+    /// Seeded by `random_seed`, so the `fixed-seed` feature makes it replayable.
+    pub fn from_random_seed() -> Self {
+        Self::new(random_seed())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Exactly uniform in `0..bound`, redrawing the top `2^64 mod bound` outcomes;
+    /// use it where `bound` may be close to `2^64`.
+    pub fn gen_below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "empty range");
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let r = self.next_u64();
+            if r < zone { return r % bound; }
+        }
+    }
+
+    /// Uniform up to a negligible modulo bias.
+    pub fn gen_range(&mut self, range: std::ops::Range<usize>) -> usize {
+        assert!(range.start < range.end, "empty range");
+        let width = (range.end - range.start) as u64;
+        range.start + (self.next_u64() % width) as usize
+    }
+}
+
+
+pub const FIXED_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/// Seed for randomized components (hash bases, priorities); `FIXED_SEED` with the
+/// `fixed-seed` feature, otherwise drawn from the clock and the address space.
+#[cfg(feature = "fixed-seed")]
+pub fn random_seed() -> u64 {
+    FIXED_SEED
+}
+
+#[cfg(not(feature = "fixed-seed"))]
+pub fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    let local = 0u8;
+    let address = &local as *const u8 as u64;
+    nanos ^ address.rotate_left(32) ^ FIXED_SEED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gen_range_bounds() {
+        let mut rng = XorShift64::new(0);
+        for _ in 0..1000 {
+            assert!((5..9).contains(&rng.gen_range(5..9)));
+        }
+    }
+
+    #[test]
+    fn test_gen_below_is_unbiased_for_large_bounds() {
+        // `next_u64() % bound` would land in the lowest third half of the time.
+        let bound = 3 << 62;
+        let mut rng = XorShift64::new(1);
+        let low = (0..3000).filter(|_| rng.gen_below(bound) < 1 << 62).count();
+        assert!((900..1100).contains(&low), "{}", low);
+        assert!((0..100).all(|_| rng.gen_below(1) == 0));
+    }
+
+    #[cfg(feature = "fixed-seed")]
+    #[test]
+    fn test_fixed_seed_replays() {
+        let a: Vec<_> = (0..5).scan(XorShift64::from_random_seed(), |rng, _| Some(rng.next_u64())).collect();
+        let b: Vec<_> = (0..5).scan(XorShift64::from_random_seed(), |rng, _| Some(rng.next_u64())).collect();
+        assert_eq!(a, b);
+        assert_eq!(random_seed(), FIXED_SEED);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+/// `result[i] = a[0] op a[1] op ... op a[i]`.
+pub fn prefix_fold<T>(a: &[T], op: impl Fn(T, T) -> T) -> Vec<T> where
+    T: Copy {
+    let mut result: Vec<T> = Vec::with_capacity(a.len());
+    for &x in a {
+        let next = match result.last() {
+            Some(&acc) => op(acc, x),
+            None => x,
+        };
+        result.push(next);
+    }
+    result
+}
+
+/// `result[i] = a[i] op a[i + 1] op ... op a[n - 1]`.
+pub fn suffix_fold<T>(a: &[T], op: impl Fn(T, T) -> T) -> Vec<T> where
+    T: Copy {
+    let mut result: Vec<T> = Vec::with_capacity(a.len());
+    for &x in a.iter().rev() {
+        let next = match result.last() {
+            Some(&acc) => op(x, acc),
+            None => x,
+        };
+        result.push(next);
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    #[test]
+    fn test_folds() {
+        let a = [3, 1, 4, 1, 5];
+        assert_eq!(prefix_fold(&a, i32::max), [3, 3, 4, 4, 5]);
+        assert_eq!(suffix_fold(&a, i32::min), [1, 1, 1, 1, 5]);
+        assert_eq!(prefix_fold(&a, |x, y| x + y), [3, 4, 8, 9, 14]);
+        assert!(prefix_fold(&[] as &[i32], i32::max).is_empty());
+
+        // Largest gcd after removing one element.
+        let b = [12, 18, 7, 24];
+        let prefix = prefix_fold(&b, gcd);
+        let suffix = suffix_fold(&b, gcd);
+        let best = (0..b.len())
+            .map(|i| {
+                let left = if i > 0 { prefix[i - 1] } else { 0 };
+                let right = if i + 1 < b.len() { suffix[i + 1] } else { 0 };
+                gcd(left, right)
+            })
+            .max();
+        assert_eq!(best, Some(6));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::cmp::Reverse;
+
+use crate::removable_heap::RemovableHeap;
+
+/// Multiset split into a lower and an upper half, the lower holding the extra element.
+#[derive(Clone)]
+pub struct RunningMedian<T> where
+    T: Ord + Clone {
+    low: RemovableHeap<T>,
+    high: RemovableHeap<Reverse<T>>,
+}
+
+impl<T> RunningMedian<T> where
+    T: Ord + Clone {
+    pub fn new() -> Self {
+        Self { low: RemovableHeap::new(), high: RemovableHeap::new() }
+    }
+
+    pub fn insert(&mut self, value: T) {
+        if self.belongs_to_low(&value) {
+            self.low.push(value);
+        } else {
+            self.high.push(Reverse(value));
+        }
+        self.rebalance();
+    }
+
+    /// `value` must currently be in the container.
+    pub fn erase(&mut self, value: T) {
+        if self.belongs_to_low(&value) {
+            self.low.remove(value);
+        } else {
+            self.high.remove(Reverse(value));
+        }
+        self.rebalance();
+    }
+
+    /// The lower median.
+    pub fn median(&mut self) -> Option<T> {
+        self.low.peek().cloned()
+    }
+
+    pub fn upper_median(&mut self) -> Option<T> {
+        if self.low.len() > self.high.len() {
+            self.low.peek().cloned()
+        } else {
+            self.high.peek().map(|Reverse(min)| min.clone())
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.low.len() + self.high.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn belongs_to_low(&mut self, value: &T) -> bool {
+        match self.low.peek() {
+            Some(max) => value <= max,
+            None => true,
+        }
+    }
+
+    fn rebalance(&mut self) {
+        if self.low.len() > self.high.len() + 1 {
+            let max = self.low.pop().unwrap();
+            self.high.push(Reverse(max));
+        } else if self.low.len() < self.high.len() {
+            let Reverse(min) = self.high.pop().unwrap();
+            self.low.push(min);
+        }
+    }
+}
+
+impl<T> Default for RunningMedian<T> where
+    T: Ord + Clone {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_erase() {
+        let mut median = RunningMedian::new();
+        assert_eq!(median.median(), None);
+
+        median.insert(5);
+        assert_eq!(median.median(), Some(5));
+        median.insert(1);
+        // {1, 5}
+        assert_eq!(median.median(), Some(1));
+        assert_eq!(median.upper_median(), Some(5));
+        median.insert(9);
+        median.insert(7);
+        median.insert(3);
+        // {1, 3, 5, 7, 9}
+        assert_eq!(median.median(), Some(5));
+
+        median.erase(5);
+        // {1, 3, 7, 9}
+        assert_eq!(median.median(), Some(3));
+        assert_eq!(median.upper_median(), Some(7));
+        median.erase(1);
+        median.erase(3);
+        // {7, 9}
+        assert_eq!(median.median(), Some(7));
+        assert_eq!(median.len(), 2);
+    }
+}
@@ -1,7 +1,17 @@
+use std::ops::RangeBounds;
+
+use crate::cast::CheckedCast;
+use crate::modint::Mod;
+use crate::monoid::{pow_monoid, Monoid, Number, SumMonoid};
+use crate::range::{to_half_open, try_to_half_open, RangeError};
+use crate::rng::XorShift64;
+
 pub trait SegmentTreeCompatible: Copy {
     fn ident() -> Self;
     fn combine(self, rhs: Self) -> Self;
     fn apply(self, rhs: Self) -> Self;
+    /// `apply` to an aggregate of `len` leaves; override for length-dependent aggregates such as sums.
+    fn apply_len(self, rhs: Self, _len: usize) -> Self { self.apply(rhs) }
     fn compose(self, rhs: Self) -> Self;
     fn apply_assign(&mut self, rhs: Self) { *self = self.apply(rhs) }
     fn compose_assign(&mut self, rhs: Self) { *self = self.compose(rhs) }
@@ -14,116 +24,333 @@ impl SegmentTreeCompatible for usize {
     fn compose(self, rhs: Self) -> Self { self + rhs }
 }
 
+/// x -> a * x + b
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Affine<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> Affine<T> where
+    T: Copy + std::ops::Add<Output = T> + std::ops::Mul<Output = T> {
+    pub fn new(a: T, b: T) -> Self {
+        Self { a, b }
+    }
+
+    pub fn eval(self, x: T) -> T {
+        self.a * x + self.b
+    }
+
+    /// x -> rhs.eval(self.eval(x))
+    pub fn then(self, rhs: Self) -> Self {
+        let a = rhs.a * self.a;
+        let b = rhs.a * self.b + rhs.b;
+        Self { a, b }
+    }
+}
+
+/// Range affine / range sum. As a leaf or aggregate only `sum` matters; as an update,
+/// `map` is applied to every covered element. Build leaves with `value` and updates with `action`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AffineSum<T> {
+    pub sum: T,
+    pub map: Affine<T>,
+}
+
+impl<const N: usize> AffineSum<Mod<N>> {
+    pub fn value(sum: Mod<N>) -> Self {
+        Self { sum, map: Affine::new(Mod::new(1), Mod::new(0)) }
+    }
+
+    pub fn action(map: Affine<Mod<N>>) -> Self {
+        Self { sum: Mod::new(0), map }
+    }
+}
+
+impl<const N: usize> SegmentTreeCompatible for AffineSum<Mod<N>> {
+    fn ident() -> Self { Self::value(Mod::new(0)) }
+    fn combine(self, rhs: Self) -> Self { Self::value(self.sum + rhs.sum) }
+    fn apply(self, rhs: Self) -> Self { self.apply_len(rhs, 1) }
+    fn apply_len(self, rhs: Self, len: usize) -> Self {
+        Self { sum: rhs.map.a * self.sum + rhs.map.b * Mod::new(len), map: self.map }
+    }
+    fn compose(self, rhs: Self) -> Self { Self::action(self.map.then(rhs.map)) }
+}
+
+/// Range assignment over any `Monoid`, e.g. point set / range composite with `Assign<Affine<_>>`.
+/// `None` is both the identity aggregate and the no-op update, so every real value stays
+/// assignable; an update `Some(x)` sets every covered leaf to `x`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Assign<M>(pub Option<M>);
+
+impl<M> SegmentTreeCompatible for Assign<M> where
+    M: Monoid {
+    fn ident() -> Self { Self(None) }
+    fn combine(self, rhs: Self) -> Self {
+        match (self.0, rhs.0) {
+            (Some(x), Some(y)) => Self(Some(x.combine(y))),
+            (x, y) => Self(x.or(y)),
+        }
+    }
+    fn apply(self, rhs: Self) -> Self { Self(rhs.0.or(self.0)) }
+    /// `len` copies of the assigned value, combined by repeated squaring.
+    fn apply_len(self, rhs: Self, len: usize) -> Self {
+        match rhs.0 {
+            None => self,
+            Some(x) => Self(Some(pow_monoid(x, len as u64, M::ident(), |a, b| a.combine(*b)))),
+        }
+    }
+    fn compose(self, rhs: Self) -> Self { Self(rhs.0.or(self.0)) }
+}
+
+impl<const N: usize> Monoid for Affine<Mod<N>> {
+    fn ident() -> Self { Self::new(Mod::new(1), Mod::new(0)) }
+    fn combine(self, rhs: Self) -> Self { self.then(rhs) }
+}
+
 #[derive(Clone)]
 pub struct SegmentTree<T> where
     T: SegmentTreeCompatible {
     size: usize,
-    values: Vec<T>, // 1-indexed
-    thunks: Vec<T>, // 1-indexed
+    log: u32,
+    values: Vec<T>, // 1-indexed, leaves at `1 << log`
+    thunks: Vec<T>, // pending for the children of each inner node
+    lens: Vec<usize>, // number of real leaves under each node
 }
 
 impl<T> SegmentTree<T> where 
     T: SegmentTreeCompatible {
     pub fn new(size: usize) -> Self {
-        let tree_size = 2 * size.next_power_of_two();
-        let values = vec![T::ident(); tree_size];
-        let thunks = vec![T::ident(); tree_size];
-        Self { size, values, thunks }
+        let log = size.next_power_of_two().trailing_zeros();
+        let capacity = 1 << log;
+        let values = vec![T::ident(); 2 * capacity];
+        let thunks = vec![T::ident(); capacity];
+        let mut lens = vec![0; 2 * capacity];
+        lens[capacity..capacity + size].fill(1);
+        for k in (1..capacity).rev() {
+            lens[k] = lens[2 * k] + lens[2 * k + 1];
+        }
+        Self { size, log, values, thunks, lens }
     }
 
-    pub fn update(&mut self, begin: usize, end: usize, value: T) {
-        let state = self.root();
-        self._update(begin, end, value, state);
+    /// Builds the tree over `leaves` in O(n).
+    pub fn from_slice(leaves: &[T]) -> Self {
+        let mut result = Self::new(leaves.len());
+        let capacity = result.thunks.len();
+        result.values[capacity..capacity + leaves.len()].copy_from_slice(leaves);
+        for k in (1..capacity).rev() {
+            result.recalc(k);
+        }
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Aggregate over all leaves. The root never has pending updates, so this is O(1).
+    pub fn all(&self) -> T {
+        self.values[1]
+    }
+
+    /// Panics if `range` is reversed or exceeds the size.
+    pub fn update(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let (begin, end) = to_half_open(range, self.size);
+        self.update_half_open(begin, end, value);
+    }
+
+    pub fn try_update(&mut self, range: impl RangeBounds<usize>, value: T) -> Result<(), RangeError> {
+        let (begin, end) = try_to_half_open(range, self.size)?;
+        self.update_half_open(begin, end, value);
+        Ok(())
     }
 
-    fn _update(&mut self, begin: usize, end: usize, value: T, state: TraversalState) {
-        if state.is_disjoint(begin, end) { return; }
-        if state.is_included(begin, end) {
-            self.thunks[state.idx].compose_assign(value);
-            self.push(state);
-            return;
+    fn update_half_open(&mut self, begin: usize, end: usize, value: T) {
+        if begin == end { return; }
+        let (begin, end) = (begin + self.thunks.len(), end + self.thunks.len());
+        self.push_boundaries(begin, end);
+        let (mut l, mut r) = (begin, end);
+        while l < r {
+            if l & 1 == 1 {
+                self.apply_node(l, value);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self.apply_node(r, value);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        for i in 1..=self.log {
+            if (begin >> i) << i != begin { self.recalc(begin >> i); }
+            if (end >> i) << i != end { self.recalc((end - 1) >> i); }
         }
-        self.push(state);
-        let left = state.left_child();
-        let right = state.right_child();
-        self._update(begin, end, value, left);
-        self._update(begin, end, value, right);
-        self.values[state.idx] = self.values[left.idx].combine(self.values[right.idx]);
     }
 
-    pub fn query(&mut self, begin: usize, end: usize) -> T {
-        let state = self.root();
-        self._query(begin, end, state)
+    /// Panics if `range` is reversed or exceeds the size.
+    pub fn query(&mut self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.size);
+        self.query_half_open(begin, end)
     }
 
-    fn _query(&mut self, begin: usize, end: usize, state: TraversalState) -> T {
-        if state.is_disjoint(begin, end) { return T::ident(); }
-        self.push(state);
-        if state.is_included(begin, end) {
-            return self.values[state.idx];
+    pub fn try_query(&mut self, range: impl RangeBounds<usize>) -> Result<T, RangeError> {
+        let (begin, end) = try_to_half_open(range, self.size)?;
+        Ok(self.query_half_open(begin, end))
+    }
+
+    fn query_half_open(&mut self, begin: usize, end: usize) -> T {
+        if begin == end { return T::ident(); }
+        let (mut l, mut r) = (begin + self.thunks.len(), end + self.thunks.len());
+        self.push_boundaries(l, r);
+        let mut left = T::ident();
+        let mut right = T::ident();
+        while l < r {
+            if l & 1 == 1 {
+                left = left.combine(self.values[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = self.values[r].combine(right);
+            }
+            l >>= 1;
+            r >>= 1;
         }
-        let left = self._query(begin, end, state.left_child());
-        let right = self._query(begin, end, state.right_child());
         left.combine(right)
     }
 
-    fn root(&self) -> TraversalState {
-        TraversalState { idx: 1, begin: 0, end: self.size }
+    /// Exact value of leaf `i`, pushing the pending updates on its path.
+    pub fn get(&mut self, i: usize) -> T {
+        assert!(i < self.size, "index {} out of range for size {}", i, self.size);
+        let leaf = i + self.thunks.len();
+        for shift in (1..=self.log).rev() {
+            self.push(leaf >> shift);
+        }
+        self.values[leaf]
     }
 
-    fn push(&mut self, state: TraversalState) {
-        let thunk = self.thunks[state.idx];
-        self.values[state.idx].apply_assign(thunk);
-        self.thunks[state.idx] = T::ident();
-        if !state.is_leaf() {
-            self.thunks[state.left_child().idx].compose_assign(thunk);
-            self.thunks[state.right_child().idx].compose_assign(thunk);
+    /// Largest `p` with `pred(query(..p))`, assuming `pred` holds for a prefix of the
+    /// positions `0..=len` (as for nondecreasing prefix sums). O(log n).
+    pub fn partition_point(&mut self, pred: impl Fn(T) -> bool) -> usize {
+        if self.size == 0 { return 0; }
+        let capacity = self.thunks.len();
+        for shift in (1..=self.log).rev() {
+            self.push(capacity >> shift);
+        }
+        let mut k = capacity;
+        let mut acc = T::ident();
+        loop {
+            while k & 1 == 0 {
+                k >>= 1;
+            }
+            let next = acc.combine(self.values[k]);
+            if !pred(next) {
+                // Padded leaves are identities, so the failing leaf is a real one.
+                while k < capacity {
+                    self.push(k);
+                    k *= 2;
+                    let next = acc.combine(self.values[k]);
+                    if pred(next) {
+                        acc = next;
+                        k += 1;
+                    }
+                }
+                return k - capacity;
+            }
+            acc = next;
+            k += 1;
+            if k.is_power_of_two() { return self.size; }
         }
     }
-}
 
-#[derive(Clone, Copy)]
-struct TraversalState {
-    idx: usize,
-    begin: usize,
-    end: usize,
-}
+    /// Pushes every pending update down and returns the leaf values.
+    pub fn to_vec(&mut self) -> Vec<T> {
+        for k in 1..self.thunks.len() {
+            self.push(k);
+        }
+        let capacity = self.thunks.len();
+        self.values[capacity..capacity + self.size].to_vec()
+    }
 
-impl TraversalState {
-    fn length(&self) -> usize {
-        self.end - self.begin
+    fn push_boundaries(&mut self, begin: usize, end: usize) {
+        for i in (1..=self.log).rev() {
+            if (begin >> i) << i != begin { self.push(begin >> i); }
+            if (end >> i) << i != end { self.push((end - 1) >> i); }
+        }
     }
 
-    fn is_leaf(&self) -> bool {
-        self.length() == 1
+    fn recalc(&mut self, k: usize) {
+        self.values[k] = self.values[2 * k].combine(self.values[2 * k + 1]);
     }
 
-    fn is_disjoint(&self, begin: usize, end: usize) -> bool {
-        self.begin >= end || self.end <= begin
+    fn apply_node(&mut self, k: usize, value: T) {
+        self.values[k] = self.values[k].apply_len(value, self.lens[k]);
+        if k < self.thunks.len() {
+            self.thunks[k].compose_assign(value);
+        }
     }
 
-    fn is_included(&self, begin: usize, end: usize) -> bool {
-        self.begin >= begin && self.end <= end
+    fn push(&mut self, k: usize) {
+        let thunk = self.thunks[k];
+        self.apply_node(2 * k, thunk);
+        self.apply_node(2 * k + 1, thunk);
+        self.thunks[k] = T::ident();
     }
+}
 
-    fn left_child(&self) -> Self {
-        let idx = self.idx * 2;
-        let begin = self.begin;
-        let end = (self.begin + self.end) / 2;
-        Self { idx, begin, end }
+impl<T> SegmentTree<SumMonoid<T>> where
+    T: Number + CheckedCast + TryFrom<u64> {
+    /// With nonnegative values, the index `i` such that `sum(..i) <= x < sum(..=i)`,
+    /// or the length if `x >= sum(..)`.
+    pub fn select_by_prefix(&mut self, x: T) -> usize {
+        self.partition_point(|sum| sum.0 <= x)
     }
 
-    fn right_child(&self) -> Self {
-        let idx = self.idx * 2 + 1;
-        let begin = (self.begin + self.end) / 2;
-        let end = self.end;
-        Self { idx, begin, end }
+    /// Picks an index with probability proportional to its (nonnegative) value.
+    pub fn sample(&mut self, rng: &mut XorShift64) -> usize {
+        let total = self.all().0;
+        assert!(total > T::ZERO, "cannot sample from a nonpositive total weight");
+        let x = T::try_from(rng.gen_below(total.as_u64())).ok().expect("sample below total fits in T");
+        self.select_by_prefix(x)
+    }
+}
+
+/// Prints the leaf values with every pending update applied.
+impl<T> std::fmt::Debug for SegmentTree<T> where
+    T: SegmentTreeCompatible + std::fmt::Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.clone().to_vec()).finish()
+    }
+}
+
+impl<T> From<Vec<T>> for SegmentTree<T> where
+    T: SegmentTreeCompatible {
+    fn from(leaves: Vec<T>) -> Self {
+        Self::from_slice(&leaves)
+    }
+}
+
+impl<T> FromIterator<T> for SegmentTree<T> where
+    T: SegmentTreeCompatible {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let leaves: Vec<T> = iter.into_iter().collect();
+        Self::from_slice(&leaves)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::SegmentTree;
+    use super::{Affine, AffineSum, Assign, SegmentTree, SegmentTreeCompatible};
+    use crate::range::RangeError;
+    use crate::modint::Mod;
+    use crate::naive::RangeArray;
+    use crate::monoid::SumMonoid;
+    use crate::rng::XorShift64;
 
     #[test]
     fn test_update_and_query_whole_range() {
@@ -131,20 +358,26 @@ mod tests {
         let mut segtree = SegmentTree::<usize>::new(size);
 
         // Initially, all positions are 0.
-        assert_eq!(segtree.query(0, size), 0);
+        assert_eq!(segtree.query(0..size), 0);
 
         // Update range [1,4) with +3.
-        segtree.update(1, 4, 3);
+        segtree.update(1..4, 3);
         // Query whole range should now yield maximum 3.
-        assert_eq!(segtree.query(0, size), 3);
+        assert_eq!(segtree.query(0..size), 3);
 
         // Query parts of the range.
         // Indices 0 remains 0.
-        assert_eq!(segtree.query(0, 1), 0);
+        assert_eq!(segtree.query(0..1), 0);
         // Indices 1 to 3 now have value 3.
-        assert_eq!(segtree.query(1, 4), 3);
+        assert_eq!(segtree.query(1..4), 3);
         // Index 4 remains 0.
-        assert_eq!(segtree.query(4, 5), 0);
+        assert_eq!(segtree.query(4..5), 0);
+
+        // Other range forms resolve to the same intervals.
+        assert_eq!(segtree.query(..), 3);
+        assert_eq!(segtree.query(1..=3), 3);
+        assert_eq!(segtree.query(4..), 0);
+        assert_eq!(segtree.query(..1), 0);
     }
 
     #[test]
@@ -153,22 +386,22 @@ mod tests {
         let mut segtree = SegmentTree::<usize>::new(size);
 
         // Update range [0,3) with +2.
-        segtree.update(0, 3, 2);
+        segtree.update(0..3, 2);
         // Update range [2,5) with +1.
-        segtree.update(2, 5, 1);
+        segtree.update(2..5, 1);
 
         // Now the expected values are:
         // index 0,1: 2
         // index 2: 2 + 1 = 3
         // index 3,4: 1
         // Whole range max should be 3.
-        assert_eq!(segtree.query(0, size), 3);
+        assert_eq!(segtree.query(0..size), 3);
         // Query [0,2): max 2.
-        assert_eq!(segtree.query(0, 2), 2);
+        assert_eq!(segtree.query(0..2), 2);
         // Query [2,3): single element index 2, value 3.
-        assert_eq!(segtree.query(2, 3), 3);
+        assert_eq!(segtree.query(2..3), 3);
         // Query [3,5): max 1.
-        assert_eq!(segtree.query(3, 5), 1);
+        assert_eq!(segtree.query(3..5), 1);
     }
 
     #[test]
@@ -177,9 +410,9 @@ mod tests {
         let mut segtree = SegmentTree::<usize>::new(size);
 
         // Perform several updates.
-        segtree.update(0, 4, 5);    // All indices +5
-        segtree.update(1, 3, 2);    // Indices 1,2 +2
-        segtree.update(2, 3, 3);    // Index 2 +3
+        segtree.update(0..4, 5);    // All indices +5
+        segtree.update(1..3, 2);    // Indices 1,2 +2
+        segtree.update(2..3, 3);    // Index 2 +3
 
         // Expected values:
         // index 0: 5
@@ -187,9 +420,255 @@ mod tests {
         // index 2: 5 + 2 + 3 = 10
         // index 3: 5
 
-        assert_eq!(segtree.query(0, 1), 5);
-        assert_eq!(segtree.query(1, 2), 7);
-        assert_eq!(segtree.query(2, 3), 10);
-        assert_eq!(segtree.query(3, 4), 5);
+        assert_eq!(segtree.query(0..1), 5);
+        assert_eq!(segtree.query(1..2), 7);
+        assert_eq!(segtree.query(2..3), 10);
+        assert_eq!(segtree.query(3..4), 5);
     }
-}
+
+    #[test]
+    fn test_collect() {
+        let mut segtree: SegmentTree<usize> = [4, 1, 7, 3, 2].into_iter().collect();
+
+        assert_eq!(segtree.query(..), 7);
+        assert_eq!(segtree.query(0..2), 4);
+        assert_eq!(segtree.query(3..), 3);
+
+        // Lazy updates still work on top of the initial values.
+        segtree.update(3..5, 5);
+        // values: [4, 1, 7, 8, 7]
+        assert_eq!(segtree.query(..), 8);
+        assert_eq!(segtree.query(4..5), 7);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let leaves = [2, 9, 4, 4, 1, 7];
+        let mut segtree = SegmentTree::from_slice(&leaves);
+        assert_eq!(segtree.to_vec(), leaves);
+        assert_eq!(segtree.query(2..5), 4);
+
+        let mut segtree = SegmentTree::from(leaves.to_vec());
+        segtree.update(2..5, 10);
+        assert_eq!(segtree.query(2..), 14);
+        assert_eq!(segtree.all(), 14);
+        assert_eq!(segtree.len(), 6);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let mut segtree = SegmentTree::<usize>::new(5);
+        assert_eq!(segtree.to_vec(), vec![0; 5]);
+
+        segtree.update(0..3, 2);
+        segtree.update(2..5, 1);
+        assert_eq!(segtree.to_vec(), vec![2, 2, 3, 1, 1]);
+
+        // Materializing does not disturb later queries.
+        assert_eq!(segtree.query(1..4), 3);
+
+        segtree.update(1..2, 4);
+        assert_eq!(format!("{:?}", segtree), "[2, 6, 3, 1, 1]");
+        segtree.update(.., 1);
+        assert_eq!((0..5).map(|i| segtree.get(i)).collect::<Vec<_>>(), [3, 7, 4, 2, 2]);
+    }
+
+    #[test]
+    fn test_point_set_range_composite() {
+        type M = Mod<998_244_353>;
+        let f = |a, b| Assign(Some(Affine::new(M::new(a), M::new(b))));
+        let eval = |composite: Assign<Affine<M>>, x| composite.0.map_or(x, |g| g.eval(M::new(x)).value);
+        let mut segtree = SegmentTree::<Assign<Affine<M>>>::new(4);
+
+        // f0(x) = 2x + 1, f1(x) = 3x + 4, f2(x) = x + 5, f3 stays identity.
+        segtree.update(0..1, f(2, 1));
+        segtree.update(1..2, f(3, 4));
+        segtree.update(2..3, f(1, 5));
+
+        // f1(f0(1)) = 3 * (2 * 1 + 1) + 4 = 13
+        assert_eq!(eval(segtree.query(0..2), 1), 13);
+        // f2(f1(f0(1))) = 13 + 5 = 18
+        assert_eq!(eval(segtree.query(0..4), 1), 18);
+        // f2(f1(0)) = 3 * 0 + 4 + 5 = 9
+        assert_eq!(eval(segtree.query(1..3), 0), 9);
+        assert_eq!(eval(segtree.query(3..), 7), 7);
+
+        // Setting f1(x) = 2x replaces it: f2(f1(f0(1))) = 2 * 3 + 5.
+        segtree.update(1..2, f(2, 0));
+        assert_eq!(eval(segtree.query(..), 1), 11);
+        segtree.update(1..3, Assign(None));
+        assert_eq!(eval(segtree.query(..), 1), 11);
+        // Range assignment: (x -> 2x + 1)^4 (0) = 15.
+        segtree.update(.., f(2, 1));
+        assert_eq!(eval(segtree.query(..), 0), 15);
+        assert_eq!(eval(segtree.query(1..3), 0), 3);
+    }
+
+    #[test]
+    fn test_range_assign_against_naive() {
+        type M = Mod<1_000_000_007>;
+        let mut rng = XorShift64::new(11);
+        let mut random_map = || Affine::new(M::new(rng.gen_range(0..5)), M::new(rng.gen_range(0..5)));
+        let mut naive: Vec<_> = (0..13).map(|_| random_map()).collect();
+        let mut segtree: SegmentTree<Assign<Affine<M>>> = naive.iter().map(|&f| Assign(Some(f))).collect();
+        for step in 0..500 {
+            let begin = step * 7 % 13;
+            let end = begin + 1 + step % (13 - begin);
+            if step % 2 == 0 {
+                let f = random_map();
+                segtree.update(begin..end, Assign(Some(f)));
+                naive[begin..end].fill(f);
+            } else {
+                let expected = naive[begin..end].iter().fold(M::new(1), |x, f| f.eval(x));
+                assert_eq!(segtree.query(begin..end).0.unwrap().eval(M::new(1)).value, expected.value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_affine_range_sum() {
+        type M = Mod<998_244_353>;
+        let mut segtree: SegmentTree<AffineSum<M>> = (1..=5).map(|x| AffineSum::value(M::new(x))).collect();
+        assert_eq!(segtree.query(..).sum.value, 15);
+        segtree.update(1..4, AffineSum::action(Affine::new(M::new(2), M::new(3))));
+        // values: [1, 7, 9, 11, 5]
+        assert_eq!(segtree.query(..).sum.value, 33);
+        segtree.update(..2, AffineSum::action(Affine::new(M::new(1), M::new(10))));
+        // values: [11, 17, 9, 11, 5]
+        assert_eq!(segtree.query(1..3).sum.value, 26);
+        assert_eq!(segtree.query(..).sum.value, 53);
+
+        let mut rng = XorShift64::new(5);
+        let mut naive = [M::new(0); 13];
+        let mut segtree = SegmentTree::<AffineSum<M>>::new(13);
+        for _ in 0..500 {
+            let begin = rng.gen_range(0..13);
+            let end = rng.gen_range(begin + 1..14);
+            if rng.gen_range(0..2) == 0 {
+                let f = Affine::new(M::new(rng.gen_range(0..100)), M::new(rng.gen_range(0..100)));
+                segtree.update(begin..end, AffineSum::action(f));
+                naive[begin..end].iter_mut().for_each(|x| *x = f.eval(*x));
+            } else {
+                let expected: M = naive[begin..end].iter().sum();
+                assert_eq!(segtree.query(begin..end).sum.value, expected.value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_by_prefix() {
+        let mut rng = XorShift64::new(13);
+        for size in 1..=20 {
+            let mut naive: Vec<u64> = (0..size).map(|_| rng.gen_range(0..3) as u64).collect();
+            let mut segtree: SegmentTree<SumMonoid<u64>> = naive.iter().copied().map(SumMonoid).collect();
+            for _ in 0..50 {
+                let begin = rng.gen_range(0..size);
+                let end = rng.gen_range(begin + 1..size + 1);
+                let add = rng.gen_range(0..3) as u64;
+                segtree.update(begin..end, SumMonoid(add));
+                naive[begin..end].iter_mut().for_each(|x| *x += add);
+                let total: u64 = naive.iter().sum();
+                let x = rng.gen_range(0..total as usize + 2) as u64;
+                let expected = (0..=size).take_while(|&i| naive[..i].iter().sum::<u64>() <= x).last().unwrap();
+                assert_eq!(segtree.select_by_prefix(x), expected);
+            }
+        }
+        // Max tree: the first position whose prefix maximum reaches 7.
+        let mut maxima: SegmentTree<usize> = [3, 1, 4, 1, 5, 9, 2, 6].into_iter().collect();
+        assert_eq!(maxima.partition_point(|m| m < 7), 5);
+        assert_eq!(maxima.partition_point(|m| m < 10), 8);
+        assert_eq!(SegmentTree::<usize>::new(0).partition_point(|_| true), 0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut segtree: SegmentTree<SumMonoid<i64>> = [1, 0, 3].into_iter().map(SumMonoid).collect();
+        let mut rng = XorShift64::new(7);
+        let mut counts = [0; 3];
+        for _ in 0..4000 {
+            counts[segtree.sample(&mut rng)] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > 2 * counts[0]);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_invalid_ranges() {
+        let mut segtree = SegmentTree::<usize>::from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(segtree.try_query(3..10), Err(RangeError::OutOfBounds { end: 10, len: 5 }));
+        assert_eq!(segtree.try_query(4..2), Err(RangeError::Reversed { begin: 4, end: 2 }));
+        assert_eq!(segtree.try_update(5..=5, 1), Err(RangeError::OutOfBounds { end: 6, len: 5 }));
+        assert_eq!(segtree.try_query(5..5), Ok(0));
+        assert_eq!(segtree.try_update(2..5, 1), Ok(()));
+        assert_eq!(segtree.try_query(..), Ok(6));
+    }
+
+    #[test]
+    #[should_panic(expected = "range end 10 out of bounds for length 5")]
+    fn test_query_out_of_bounds_panics() {
+        SegmentTree::<usize>::new(5).query(3..10);
+    }
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(42);
+        for size in 1..=20 {
+            let mut segtree = SegmentTree::<usize>::new(size);
+            let mut naive = RangeArray::new(vec![0; size]);
+            for _ in 0..200 {
+                let begin = rng.gen_range(0..size);
+                let end = rng.gen_range(begin + 1..size + 1);
+                if rng.next_u64() % 2 == 0 {
+                    let value = rng.gen_range(0..100);
+                    segtree.update(begin..end, value);
+                    naive.update(begin..end, value);
+                } else {
+                    assert_eq!(segtree.query(begin..end), naive.query(begin..end));
+                    assert_eq!(segtree.get(begin), naive.as_slice()[begin]);
+                }
+            }
+            assert_eq!(segtree.to_vec(), naive.as_slice());
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Sum(i64);
+
+    impl SegmentTreeCompatible for Sum {
+        fn ident() -> Self { Sum(0) }
+        fn combine(self, rhs: Self) -> Self { Sum(self.0 + rhs.0) }
+        fn apply(self, rhs: Self) -> Self { Sum(self.0 + rhs.0) }
+        fn apply_len(self, rhs: Self, len: usize) -> Self { Sum(self.0 + rhs.0 * len as i64) }
+        fn compose(self, rhs: Self) -> Self { Sum(self.0 + rhs.0) }
+    }
+
+    #[test]
+    fn test_range_add_range_sum() {
+        let mut segtree: SegmentTree<Sum> = [1, 2, 3, 4, 5].into_iter().map(Sum).collect();
+        assert_eq!(segtree.query(..), Sum(15));
+        segtree.update(1..4, Sum(10));
+        // values: [1, 12, 13, 14, 5]
+        assert_eq!(segtree.query(..), Sum(45));
+        assert_eq!(segtree.query(2..), Sum(32));
+        segtree.update(.., Sum(-1));
+        assert_eq!(segtree.all(), Sum(40));
+        assert_eq!(segtree.query(..2), Sum(11));
+        assert_eq!(segtree.to_vec(), [0, 11, 12, 13, 4].map(Sum));
+
+        let mut rng = XorShift64::new(7);
+        let mut segtree = SegmentTree::<Sum>::new(13);
+        let mut naive = RangeArray::new(vec![Sum(0); 13]);
+        for _ in 0..500 {
+            let begin = rng.gen_range(0..13);
+            let end = rng.gen_range(begin + 1..14);
+            if rng.next_u64() % 2 == 0 {
+                let value = Sum(rng.gen_range(0..100) as i64 - 50);
+                segtree.update(begin..end, value);
+                naive.update(begin..end, value);
+            } else {
+                assert_eq!(segtree.query(begin..end), naive.query(begin..end));
+            }
+        }
+    }
+}
\ No newline at end of file
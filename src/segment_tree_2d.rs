@@ -0,0 +1,184 @@
+use std::ops::RangeBounds;
+
+use crate::grid::Grid;
+use crate::monoid::Monoid;
+use crate::range::to_half_open;
+
+/// Dense 2D segment tree with point assignment and rectangle `combine`.
+/// `combine` must be commutative. O(HW) memory, O(log H log W) per operation.
+#[derive(Clone)]
+pub struct SegmentTree2D<T> where
+    T: Monoid {
+    height: usize,
+    width: usize,
+    row_offset: usize,
+    col_offset: usize,
+    values: Vec<T>, // (2 * row_offset) x (2 * col_offset), row-major
+}
+
+impl<T> SegmentTree2D<T> where
+    T: Monoid {
+    pub fn new(size: (usize, usize)) -> Self {
+        let (height, width) = size;
+        let row_offset = height.next_power_of_two();
+        let col_offset = width.next_power_of_two();
+        let values = vec![T::ident(); 4 * row_offset * col_offset];
+        Self { height, width, row_offset, col_offset, values }
+    }
+
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        let mut result = Self::new(grid.size());
+        for i in 0..result.height {
+            let begin = result.idx(result.row_offset + i, result.col_offset);
+            result.values[begin..begin + result.width].copy_from_slice(grid.row(i));
+        }
+        for r in (1..2 * result.row_offset).rev() {
+            if r < result.row_offset {
+                for c in 1..2 * result.col_offset {
+                    result.update_column(r, c);
+                }
+            } else {
+                for c in (1..result.col_offset).rev() {
+                    result.update_row(r, c);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        assert!(i < self.height && j < self.width, "({}, {}) out of range for {}x{}", i, j, self.height, self.width);
+        let mut r = self.row_offset + i;
+        let leaf = self.idx(r, self.col_offset + j);
+        self.values[leaf] = value;
+        loop {
+            if r < self.row_offset {
+                let mut c = self.col_offset + j;
+                while c >= 1 {
+                    self.update_column(r, c);
+                    c /= 2;
+                }
+            } else {
+                let mut c = (self.col_offset + j) / 2;
+                while c >= 1 {
+                    self.update_row(r, c);
+                    c /= 2;
+                }
+            }
+            if r == 1 { break; }
+            r /= 2;
+        }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> T {
+        assert!(i < self.height && j < self.width, "({}, {}) out of range for {}x{}", i, j, self.height, self.width);
+        self.values[self.idx(self.row_offset + i, self.col_offset + j)]
+    }
+
+    pub fn query(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> T {
+        let (top, bottom) = to_half_open(rows, self.height);
+        let (left, right) = to_half_open(cols, self.width);
+        let mut result = T::ident();
+        let (mut lo, mut hi) = (top + self.row_offset, bottom + self.row_offset);
+        while lo < hi {
+            if lo & 1 == 1 {
+                result = result.combine(self.query_row(lo, left, right));
+                lo += 1;
+            }
+            if hi & 1 == 1 {
+                hi -= 1;
+                result = result.combine(self.query_row(hi, left, right));
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        result
+    }
+
+    fn query_row(&self, r: usize, begin: usize, end: usize) -> T {
+        let mut result = T::ident();
+        let (mut lo, mut hi) = (begin + self.col_offset, end + self.col_offset);
+        while lo < hi {
+            if lo & 1 == 1 {
+                result = result.combine(self.values[self.idx(r, lo)]);
+                lo += 1;
+            }
+            if hi & 1 == 1 {
+                hi -= 1;
+                result = result.combine(self.values[self.idx(r, hi)]);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        result
+    }
+
+    fn idx(&self, r: usize, c: usize) -> usize {
+        r * 2 * self.col_offset + c
+    }
+
+    fn update_row(&mut self, r: usize, c: usize) {
+        let value = self.values[self.idx(r, 2 * c)].combine(self.values[self.idx(r, 2 * c + 1)]);
+        let idx = self.idx(r, c);
+        self.values[idx] = value;
+    }
+
+    fn update_column(&mut self, r: usize, c: usize) {
+        let value = self.values[self.idx(2 * r, c)].combine(self.values[self.idx(2 * r + 1, c)]);
+        let idx = self.idx(r, c);
+        self.values[idx] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Count(usize);
+
+    impl Monoid for Count {
+        fn ident() -> Self { Count(0) }
+        fn combine(self, rhs: Self) -> Self { Count(self.0 + rhs.0) }
+    }
+
+    #[test]
+    fn test_count_points_in_rectangle() {
+        let mut segtree = SegmentTree2D::new((4, 5));
+        for (i, j) in [(0, 0), (1, 3), (2, 2), (3, 4), (3, 0)] {
+            segtree.set(i, j, Count(1));
+        }
+        assert_eq!(segtree.query(.., ..), Count(5));
+        assert_eq!(segtree.query(1..3, 2..4), Count(2));
+        assert_eq!(segtree.query(3.., ..), Count(2));
+        segtree.set(2, 2, Count(0));
+        assert_eq!(segtree.query(1..3, 2..4), Count(1));
+        assert_eq!(segtree.get(1, 3), Count(1));
+    }
+
+    #[test]
+    fn test_random_max_against_naive() {
+        let mut rng = XorShift64::new(5);
+        let (height, width) = (6, 7);
+        let grid: Grid<usize> = (0..height).map(|_| (0..width).map(|_| rng.gen_range(0..100)).collect()).collect();
+        let mut segtree = SegmentTree2D::from_grid(&grid);
+        let mut naive: Vec<Vec<usize>> = (0..height).map(|i| grid.row(i).to_vec()).collect();
+        for _ in 0..500 {
+            let (i, j) = (rng.gen_range(0..height), rng.gen_range(0..width));
+            if rng.next_u64() % 2 == 0 {
+                let value = rng.gen_range(0..100);
+                segtree.set(i, j, value);
+                naive[i][j] = value;
+            } else {
+                let (bottom, right) = (rng.gen_range(i + 1..height + 1), rng.gen_range(j + 1..width + 1));
+                let expected = naive[i..bottom].iter().flat_map(|row| &row[j..right]).copied().max().unwrap();
+                assert_eq!(segtree.query(i..bottom, j..right), expected);
+            }
+        }
+    }
+}
\ No newline at end of file
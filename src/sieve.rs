@@ -0,0 +1,141 @@
+/// Linear sieve: smallest prime factors of `0..=n`, for factorizing any `x <= n` in O(log x).
+#[derive(Clone)]
+pub struct Sieve {
+    spf: Vec<usize>, // spf[0] == spf[1] == 0
+    primes: Vec<usize>,
+}
+
+impl Sieve {
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0; n + 1];
+        let mut primes = Vec::new();
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i;
+                primes.push(i);
+            }
+            for &p in &primes {
+                if p > spf[i] || i * p > n { break; }
+                spf[i * p] = p;
+            }
+        }
+        Self { spf, primes }
+    }
+
+    /// The largest value the tables cover.
+    pub fn limit(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    pub fn primes(&self) -> &[usize] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.spf[x] == x
+    }
+
+    pub fn smallest_prime_factor(&self, x: usize) -> usize {
+        self.spf[x]
+    }
+
+    /// `(prime, exponent)` pairs in increasing order of prime; `x` must be positive.
+    pub fn factorize(&self, mut x: usize) -> Vec<(usize, u32)> {
+        assert!(x >= 1, "cannot factorize 0");
+        let mut result: Vec<(usize, u32)> = Vec::new();
+        while x > 1 {
+            let p = self.spf[x];
+            match result.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => result.push((p, 1)),
+            }
+            x /= p;
+        }
+        result
+    }
+
+    /// All divisors of `x` in increasing order.
+    pub fn divisors(&self, x: usize) -> Vec<usize> {
+        let mut result = vec![1];
+        for (p, e) in self.factorize(x) {
+            let count = result.len();
+            let mut power = 1;
+            for _ in 0..e {
+                power *= p;
+                for i in 0..count {
+                    result.push(result[i] * power);
+                }
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    /// Euler's totient of a single positive `x`.
+    pub fn phi(&self, x: usize) -> usize {
+        self.factorize(x).into_iter().fold(x, |acc, (p, _)| acc / p * (p - 1))
+    }
+
+    /// `phi[x]` for every `x <= limit`, with `phi[0] == 0`.
+    pub fn phi_table(&self) -> Vec<usize> {
+        let mut phi = vec![0; self.spf.len()];
+        if phi.len() > 1 {
+            phi[1] = 1;
+        }
+        for x in 2..phi.len() {
+            let p = self.spf[x];
+            let rest = x / p;
+            phi[x] = if rest % p == 0 { phi[rest] * p } else { phi[rest] * (p - 1) };
+        }
+        phi
+    }
+
+    /// Möbius function for every `x <= limit`, with `mu[0] == 0`.
+    pub fn mobius_table(&self) -> Vec<i8> {
+        let mut mu = vec![0; self.spf.len()];
+        if mu.len() > 1 {
+            mu[1] = 1;
+        }
+        for x in 2..mu.len() {
+            let p = self.spf[x];
+            let rest = x / p;
+            mu[x] = if rest % p == 0 { 0 } else { -mu[rest] };
+        }
+        mu
+    }
+}
+
+pub fn primes_up_to(n: usize) -> Vec<usize> {
+    Sieve::new(n).primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sieve() {
+        assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(primes_up_to(1).is_empty());
+
+        let sieve = Sieve::new(1000);
+        assert_eq!(sieve.factorize(360), [(2, 3), (3, 2), (5, 1)]);
+        assert!(sieve.factorize(1).is_empty());
+        assert_eq!(sieve.divisors(12), [1, 2, 3, 4, 6, 12]);
+        assert_eq!(sieve.divisors(1), [1]);
+        assert!(sieve.is_prime(997) && !sieve.is_prime(999) && !sieve.is_prime(1));
+
+        let phi = sieve.phi_table();
+        let mu = sieve.mobius_table();
+        for x in 1..=1000 {
+            let naive_phi = (1..=x).filter(|&y| crate::number_theory::gcd(x as u64, y as u64) == 1).count();
+            assert_eq!(phi[x], naive_phi);
+            assert_eq!(sieve.phi(x), naive_phi);
+            let factors = sieve.factorize(x);
+            let squarefree = factors.iter().all(|&(_, e)| e == 1);
+            let naive_mu = if !squarefree { 0 } else if factors.len() % 2 == 0 { 1 } else { -1 };
+            assert_eq!(mu[x], naive_mu);
+            assert_eq!(sieve.divisors(x).len(), factors.iter().map(|&(_, e)| e as usize + 1).product());
+        }
+    }
+}
\ No newline at end of file
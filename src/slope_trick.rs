@@ -0,0 +1,150 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Convex piecewise-linear f: i64 -> i64 as its minimum plus the breakpoints
+/// left and right of the minimum (each with slope change 1).
+#[derive(Clone, Default)]
+pub struct SlopeTrick {
+    min: i64,
+    left: BinaryHeap<i64>,
+    right: BinaryHeap<Reverse<i64>>,
+    add_left: i64,
+    add_right: i64,
+}
+
+impl SlopeTrick {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min(&self) -> i64 {
+        self.min
+    }
+
+    /// f(x) += a
+    pub fn add_all(&mut self, a: i64) {
+        self.min += a;
+    }
+
+    /// f(x) += max(0, x - a)
+    pub fn add_x_minus_a(&mut self, a: i64) {
+        if let Some(l) = self.top_left() {
+            self.min += (l - a).max(0);
+        }
+        self.push_left(a);
+        let l = self.pop_left();
+        self.push_right(l);
+    }
+
+    /// f(x) += max(0, a - x)
+    pub fn add_a_minus_x(&mut self, a: i64) {
+        if let Some(r) = self.top_right() {
+            self.min += (a - r).max(0);
+        }
+        self.push_right(a);
+        let r = self.pop_right();
+        self.push_left(r);
+    }
+
+    /// f(x) += |x - a|
+    pub fn add_abs(&mut self, a: i64) {
+        self.add_x_minus_a(a);
+        self.add_a_minus_x(a);
+    }
+
+    /// f(x) <- min_{y <= x} f(y)
+    pub fn prefix_min(&mut self) {
+        self.right.clear();
+    }
+
+    /// f(x) <- min_{y >= x} f(y)
+    pub fn suffix_min(&mut self) {
+        self.left.clear();
+    }
+
+    /// f(x) <- min_{x - b <= y <= x - a} f(y)
+    pub fn sliding_window_min(&mut self, a: i64, b: i64) {
+        assert!(a <= b, "window must not be empty");
+        self.add_left += a;
+        self.add_right += b;
+    }
+
+    /// f(x) <- f(x - a)
+    pub fn shift(&mut self, a: i64) {
+        self.sliding_window_min(a, a);
+    }
+
+    /// O(number of breakpoints).
+    pub fn eval(&self, x: i64) -> i64 {
+        let from_left: i64 = self.left.iter().map(|&l| (l + self.add_left - x).max(0)).sum();
+        let from_right: i64 = self.right.iter().map(|&Reverse(r)| (x - r - self.add_right).max(0)).sum();
+        self.min + from_left + from_right
+    }
+
+    fn top_left(&self) -> Option<i64> {
+        self.left.peek().map(|&l| l + self.add_left)
+    }
+
+    fn top_right(&self) -> Option<i64> {
+        self.right.peek().map(|&Reverse(r)| r + self.add_right)
+    }
+
+    fn push_left(&mut self, a: i64) {
+        self.left.push(a - self.add_left);
+    }
+
+    fn push_right(&mut self, a: i64) {
+        self.right.push(Reverse(a - self.add_right));
+    }
+
+    fn pop_left(&mut self) -> i64 {
+        self.left.pop().unwrap() + self.add_left
+    }
+
+    fn pop_right(&mut self) -> i64 {
+        self.right.pop().unwrap().0 + self.add_right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_abs() {
+        let mut f = SlopeTrick::new();
+        for a in [3, 8, 1] {
+            f.add_abs(a);
+        }
+        // |x - 1| + |x - 3| + |x - 8| is minimized at the median 3.
+        assert_eq!(f.min(), 7);
+        assert_eq!(f.eval(3), 7);
+        assert_eq!(f.eval(0), 1 + 3 + 8);
+        assert_eq!(f.eval(10), 9 + 7 + 2);
+    }
+
+    #[test]
+    fn test_make_non_decreasing() {
+        // Minimum total |a_i - b_i| over non-decreasing sequences b.
+        let a = [5, 1, 4, 2, 3];
+        let mut f = SlopeTrick::new();
+        for &x in &a {
+            f.prefix_min();
+            f.add_abs(x);
+        }
+        // e.g. b = [2, 2, 3, 3, 3]: 3 + 1 + 1 + 1 + 0
+        assert_eq!(f.min(), 6);
+    }
+
+    #[test]
+    fn test_sliding_window_min() {
+        let mut f = SlopeTrick::new();
+        f.add_abs(0);
+        f.sliding_window_min(-2, 3);
+        // f(x) = distance from x to [-2, 3]
+        assert_eq!(f.min(), 0);
+        assert_eq!(f.eval(-4), 2);
+        assert_eq!(f.eval(1), 0);
+        assert_eq!(f.eval(5), 2);
+    }
+}
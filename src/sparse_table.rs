@@ -0,0 +1,132 @@
+use std::ops::RangeBounds;
+
+use crate::range::to_half_open;
+
+/// Static range queries for an idempotent, associative `op` (min, max, gcd, and, or):
+/// O(n log n) build, O(1) query.
+#[derive(Clone)]
+pub struct SparseTable<T, F> where
+    F: Fn(T, T) -> T {
+    table: Vec<Vec<T>>, // table[j][i] = op over values[i..i + 2^j]
+    op: F,
+}
+
+impl<T, F> SparseTable<T, F> where
+    T: Copy,
+    F: Fn(T, T) -> T {
+    pub fn new(values: &[T], op: F) -> Self {
+        let n = values.len();
+        let mut table = vec![values.to_vec()];
+        let mut width = 1;
+        while 2 * width <= n {
+            let last = table.last().unwrap();
+            let next = (0..=n - 2 * width).map(|i| op(last[i], last[i + width])).collect();
+            table.push(next);
+            width *= 2;
+        }
+        Self { table, op }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table[0].is_empty()
+    }
+
+    /// Panics if `range` is empty.
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.len());
+        assert!(begin < end, "query range must be non-empty");
+        let level = (end - begin).ilog2() as usize;
+        (self.op)(self.table[level][begin], self.table[level][end - (1 << level)])
+    }
+}
+
+/// Static range folds for any associative `op`, commutative or not: O(n log n) build,
+/// O(1) query with a single `op` call.
+#[derive(Clone)]
+pub struct DisjointSparseTable<T, F> where
+    F: Fn(&T, &T) -> T {
+    // table[0] is the input; in table[h], each block of 2^h splits at its middle into
+    // suffix folds ending at the middle and prefix folds starting from it.
+    table: Vec<Vec<T>>,
+    op: F,
+}
+
+impl<T, F> DisjointSparseTable<T, F> where
+    T: Clone,
+    F: Fn(&T, &T) -> T {
+    pub fn new(values: &[T], op: F) -> Self {
+        let n = values.len();
+        let mut table = vec![values.to_vec()];
+        let mut half = 1;
+        while half < n {
+            let mut level = values.to_vec();
+            for mid in (half..n).step_by(2 * half) {
+                for i in (mid - half..mid - 1).rev() {
+                    level[i] = op(&values[i], &level[i + 1]);
+                }
+                for i in mid + 1..n.min(mid + half) {
+                    level[i] = op(&level[i - 1], &values[i]);
+                }
+            }
+            table.push(level);
+            half *= 2;
+        }
+        Self { table, op }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table[0].is_empty()
+    }
+
+    /// Panics if `range` is empty.
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (begin, end) = to_half_open(range, self.len());
+        assert!(begin < end, "query range must be non-empty");
+        let last = end - 1;
+        if begin == last { return self.table[0][begin].clone(); }
+        let level = (begin ^ last).ilog2() as usize + 1;
+        (self.op)(&self.table[level][begin], &self.table[level][last])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisjointSparseTable, SparseTable};
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(17);
+        for n in 1..40 {
+            let values: Vec<u32> = (0..n).map(|_| rng.gen_range(0..1000) as u32).collect();
+            let minima = SparseTable::new(&values, u32::min);
+            let ors = SparseTable::new(&values, |a, b| a | b);
+            for begin in 0..n {
+                for end in begin + 1..=n {
+                    assert_eq!(minima.query(begin..end), *values[begin..end].iter().min().unwrap());
+                    assert_eq!(ors.query(begin..end), values[begin..end].iter().fold(0, |acc, &x| acc | x));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_disjoint_non_commutative() {
+        // Strings under concatenation: associative but not commutative.
+        let words: Vec<String> = "abcdefghijk".chars().map(String::from).collect();
+        let table = DisjointSparseTable::new(&words, |a: &String, b: &String| format!("{}{}", a, b));
+        for begin in 0..words.len() {
+            for end in begin + 1..=words.len() {
+                assert_eq!(table.query(begin..end), words[begin..end].concat());
+            }
+        }
+    }
+}
\ No newline at end of file
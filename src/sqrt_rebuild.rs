@@ -0,0 +1,78 @@
+/// Keeps a static structure built from all but the latest items and rebuilds it
+/// once `bucket` items are buffered; queries see both the structure and the buffer.
+pub struct SqrtRebuild<T, S, B> where
+    B: Fn(&[T]) -> S {
+    items: Vec<T>,
+    pending: Vec<T>,
+    built: S,
+    bucket: usize,
+    build: B,
+}
+
+impl<T, S, B> SqrtRebuild<T, S, B> where
+    B: Fn(&[T]) -> S {
+    pub fn new(bucket: usize, build: B) -> Self {
+        assert!(bucket > 0, "bucket size must be positive");
+        let built = build(&[]);
+        Self { items: Vec::new(), pending: Vec::new(), built, bucket, build }
+    }
+
+    pub fn insert(&mut self, item: T) {
+        self.pending.push(item);
+        if self.pending.len() >= self.bucket {
+            self.rebuild();
+        }
+    }
+
+    pub fn rebuild(&mut self) {
+        self.items.append(&mut self.pending);
+        self.built = (self.build)(&self.items);
+    }
+
+    pub fn query<R>(&self, f: impl FnOnce(&S, &[T]) -> R) -> R {
+        f(&self.built, &self.pending)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() + self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nearest(points: &SqrtRebuild<i64, Vec<i64>, impl Fn(&[i64]) -> Vec<i64>>, x: i64) -> Option<i64> {
+        points.query(|sorted, pending| {
+            let idx = sorted.partition_point(|&p| p < x);
+            let candidates = sorted[idx.saturating_sub(1)..(idx + 1).min(sorted.len())].iter();
+            candidates.chain(pending).map(|&p| (p - x).abs()).min()
+        })
+    }
+
+    #[test]
+    fn test_dynamic_nearest_point() {
+        let build = |items: &[i64]| {
+            let mut sorted = items.to_vec();
+            sorted.sort_unstable();
+            sorted
+        };
+        let mut points = SqrtRebuild::new(3, build);
+        assert_eq!(nearest(&points, 0), None);
+
+        let mut naive = Vec::new();
+        for (i, &p) in [50, -20, 7, 31, 100, -3, 64, 12].iter().enumerate() {
+            points.insert(p);
+            naive.push(p);
+            for x in -30..=110 {
+                let expected = naive.iter().map(|&q: &i64| (q - x).abs()).min();
+                assert_eq!(nearest(&points, x), expected, "after {} inserts, x = {}", i + 1, x);
+            }
+        }
+        assert_eq!(points.len(), 8);
+    }
+}
@@ -0,0 +1,179 @@
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+use crate::range::to_half_open;
+use crate::sparse_table::SparseTable;
+
+/// Starting indices of the suffixes of `s` in lexicographic order, by prefix doubling. O(n log n).
+pub fn suffix_array<T>(s: &[T]) -> Vec<usize> where
+    T: Ord {
+    let n = s.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by(|&i, &j| s[i].cmp(&s[j]));
+    let mut rank = vec![0; n];
+    for k in 1..n {
+        rank[sa[k]] = rank[sa[k - 1]] + usize::from(s[sa[k]] != s[sa[k - 1]]);
+    }
+    let mut shifted = Vec::with_capacity(n);
+    let mut count = Vec::new();
+    let mut next_rank = vec![0; n];
+    let mut width = 1;
+    while width < n && rank[sa[n - 1]] + 1 < n {
+        // Order by (rank[i], rank[i + width]): suffixes shorter than `width` come first,
+        // the rest in the order of their second half, then a stable counting sort on the first.
+        shifted.clear();
+        shifted.extend(n - width..n);
+        shifted.extend(sa.iter().filter(|&&i| i >= width).map(|&i| i - width));
+        count.clear();
+        count.resize(n + 1, 0);
+        for &i in &shifted {
+            count[rank[i] + 1] += 1;
+        }
+        for r in 1..=n {
+            count[r] += count[r - 1];
+        }
+        for &i in &shifted {
+            sa[count[rank[i]]] = i;
+            count[rank[i]] += 1;
+        }
+        let key = |i: usize| (rank[i], if i + width < n { Some(rank[i + width]) } else { None });
+        next_rank[sa[0]] = 0;
+        for k in 1..n {
+            next_rank[sa[k]] = next_rank[sa[k - 1]] + usize::from(key(sa[k]) != key(sa[k - 1]));
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+        width *= 2;
+    }
+    sa
+}
+
+/// `lcp[k]` is the longest common prefix of the suffixes `sa[k]` and `sa[k + 1]` (Kasai). O(n).
+pub fn lcp_array<T>(s: &[T], sa: &[usize]) -> Vec<usize> where
+    T: Eq {
+    let n = s.len();
+    let mut rank = vec![0; n];
+    for (k, &i) in sa.iter().enumerate() {
+        rank[i] = k;
+    }
+    let mut lcp = vec![0; n.saturating_sub(1)];
+    let mut h: usize = 0;
+    for i in 0..n {
+        h = h.saturating_sub(1);
+        if rank[i] + 1 == n {
+            h = 0;
+            continue;
+        }
+        let j = sa[rank[i] + 1];
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+        lcp[rank[i]] = h;
+    }
+    lcp
+}
+
+/// Longest common prefix of any two suffixes in O(1), after O(n log n) preprocessing.
+pub struct LcpOracle {
+    sa: Vec<usize>,
+    rank: Vec<usize>,
+    lcp: SparseTable<usize, fn(usize, usize) -> usize>,
+}
+
+impl LcpOracle {
+    pub fn new<T>(s: &[T]) -> Self where
+        T: Ord {
+        let sa = suffix_array(s);
+        let mut rank = vec![0; s.len()];
+        for (k, &i) in sa.iter().enumerate() {
+            rank[i] = k;
+        }
+        let lcp = SparseTable::new(&lcp_array(s, &sa), usize::min as fn(usize, usize) -> usize);
+        Self { sa, rank, lcp }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    pub fn suffix_array(&self) -> &[usize] {
+        &self.sa
+    }
+
+    pub fn rank(&self, i: usize) -> usize {
+        self.rank[i]
+    }
+
+    /// Length of the longest common prefix of `s[i..]` and `s[j..]`.
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        if i == j { return self.len() - i; }
+        let (begin, end) = if self.rank[i] < self.rank[j] {
+            (self.rank[i], self.rank[j])
+        } else {
+            (self.rank[j], self.rank[i])
+        };
+        self.lcp.query(begin..end)
+    }
+
+    /// Lexicographic comparison of `s[lhs]` and `s[rhs]` in O(1).
+    pub fn compare_substrings(&self, lhs: impl RangeBounds<usize>, rhs: impl RangeBounds<usize>) -> Ordering {
+        let (l1, r1) = to_half_open(lhs, self.len());
+        let (l2, r2) = to_half_open(rhs, self.len());
+        let (len1, len2) = (r1 - l1, r2 - l2);
+        if len1.min(len2) == 0 || self.lcp(l1, l2) >= len1.min(len2) {
+            return len1.cmp(&len2);
+        }
+        self.rank[l1].cmp(&self.rank[l2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    fn naive_lcp(s: &[u8], i: usize, j: usize) -> usize {
+        s[i..].iter().zip(&s[j..]).take_while(|(a, b)| a == b).count()
+    }
+
+    #[test]
+    fn test_suffix_array() {
+        assert_eq!(suffix_array(b"banana"), vec![5, 3, 1, 0, 4, 2]);
+        assert_eq!(lcp_array(b"banana", &[5, 3, 1, 0, 4, 2]), vec![1, 3, 0, 0, 2]);
+        assert_eq!(suffix_array(b"aaaa"), vec![3, 2, 1, 0]);
+        assert!(suffix_array::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_compare_substrings() {
+        let oracle = LcpOracle::new(b"abracadabra");
+        assert_eq!(oracle.compare_substrings(0..4, 7..), Ordering::Equal);
+        assert_eq!(oracle.compare_substrings(..2, 7..), Ordering::Less);
+        assert_eq!(oracle.compare_substrings(1..=2, 5..7), Ordering::Greater);
+        assert_eq!(oracle.compare_substrings(3..3, 4..4), Ordering::Equal);
+        assert_eq!(oracle.compare_substrings(11.., 0..1), Ordering::Less);
+    }
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(11);
+        for _ in 0..100 {
+            let n = rng.gen_range(1..40);
+            let s: Vec<u8> = (0..n).map(|_| b'a' + rng.gen_range(0..3) as u8).collect();
+            let mut expected: Vec<usize> = (0..n).collect();
+            expected.sort_by_key(|&i| &s[i..]);
+            let oracle = LcpOracle::new(&s);
+            assert_eq!(oracle.suffix_array(), expected);
+            for i in 0..n {
+                for j in 0..n {
+                    assert_eq!(oracle.lcp(i, j), naive_lcp(&s, i, j));
+                    let (r1, r2) = (rng.gen_range(i..n + 1), rng.gen_range(j..n + 1));
+                    assert_eq!(oracle.compare_substrings(i..r1, j..r2), s[i..r1].cmp(&s[j..r2]));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,144 @@
+use std::ops::RangeBounds;
+
+use crate::queue_undo::Rollback;
+use crate::range::to_half_open;
+
+pub enum TimeEvent<'a, T> {
+    /// `item` becomes active for a block of times.
+    Enter(&'a T),
+    /// The matching `Enter` ends; exits come in reverse order of entries.
+    Exit(&'a T),
+    /// Every item alive at this time has been entered.
+    At(usize),
+}
+
+/// Segment tree over times `0..n` for offline divide and conquer: an item alive on an
+/// interval is stored at O(log n) nodes, and a DFS enters it on the way down.
+#[derive(Clone)]
+pub struct TimeSegments<T> {
+    len: usize,
+    nodes: Vec<Vec<T>>, // 1-indexed, leaves at nodes.len() / 2..
+}
+
+impl<T> TimeSegments<T> {
+    pub fn new(len: usize) -> Self {
+        let nodes = (0..2 * len.next_power_of_two()).map(|_| Vec::new()).collect();
+        Self { len, nodes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Makes `item` alive during the times in `range`.
+    pub fn insert(&mut self, range: impl RangeBounds<usize>, item: T) where
+        T: Clone {
+        let (begin, end) = to_half_open(range, self.len);
+        let cap = self.nodes.len() / 2;
+        let (mut l, mut r) = (begin + cap, end + cap);
+        while l < r {
+            if l & 1 == 1 {
+                self.nodes[l].push(item.clone());
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self.nodes[r].push(item.clone());
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+    }
+
+    /// Visits times in increasing order.
+    pub fn run(&self, mut visit: impl FnMut(TimeEvent<'_, T>)) {
+        if self.len > 0 {
+            self.dfs(1, &mut visit);
+        }
+    }
+
+    /// `run` with items applied to `state` on entry and rolled back on exit.
+    pub fn run_with_rollback<S>(&self, state: &mut S, mut at: impl FnMut(usize, &mut S)) where
+        S: Rollback<Op = T>,
+        T: Clone {
+        self.run(|event| match event {
+            TimeEvent::Enter(item) => state.apply(item.clone()),
+            TimeEvent::Exit(_) => state.rollback(),
+            TimeEvent::At(time) => at(time, state),
+        });
+    }
+
+    fn dfs(&self, node: usize, visit: &mut impl FnMut(TimeEvent<'_, T>)) {
+        let cap = self.nodes.len() / 2;
+        // leftmost time under `node`
+        let first = (node << (cap.trailing_zeros() - node.ilog2())) - cap;
+        if first >= self.len { return; }
+        for item in &self.nodes[node] {
+            visit(TimeEvent::Enter(item));
+        }
+        if node >= cap {
+            visit(TimeEvent::At(node - cap));
+        } else {
+            self.dfs(2 * node, visit);
+            self.dfs(2 * node + 1, visit);
+        }
+        for item in self.nodes[node].iter().rev() {
+            visit(TimeEvent::Exit(item));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disjoint_set::RollbackDisjointSet;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_offline_dynamic_connectivity() {
+        let mut rng = XorShift64::new(22);
+        let (n, times) = (6, 40);
+        let mut segments = TimeSegments::new(times);
+        let mut alive = vec![Vec::new(); times];
+        for _ in 0..15 {
+            let edge = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let begin = rng.gen_range(0..times);
+            let end = rng.gen_range(begin..times + 1);
+            segments.insert(begin..end, edge);
+            for edges in &mut alive[begin..end] {
+                edges.push(edge);
+            }
+        }
+        let mut visited = Vec::new();
+        segments.run_with_rollback(&mut RollbackDisjointSet::new(n), |time, dsu| {
+            let mut naive = RollbackDisjointSet::new(n);
+            for &(u, v) in &alive[time] {
+                naive.union(u, v);
+            }
+            for u in 0..n {
+                assert_eq!(dsu.size(u), naive.size(u));
+            }
+            visited.push(time);
+        });
+        assert_eq!(visited, (0..times).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_events_nest() {
+        let mut segments = TimeSegments::new(3);
+        segments.insert(0..3, 'a');
+        segments.insert(1..2, 'b');
+        let mut log = String::new();
+        segments.run(|event| match event {
+            TimeEvent::Enter(c) => log.push(c.to_ascii_uppercase()),
+            TimeEvent::Exit(&c) => log.push(c),
+            TimeEvent::At(t) => log.push_str(&t.to_string()),
+        });
+        // `a` is split across the two halves of the tree.
+        assert_eq!(log, "A0B1baA2a");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use std::cmp::Reverse;
+use std::ops::{AddAssign, SubAssign};
+
+use crate::removable_heap::RemovableHeap;
+
+/// Multiset that keeps its `k` largest elements separated from the rest,
+/// maintaining their sum.
+#[derive(Clone)]
+pub struct TopK<T> where
+    T: Ord + Clone + Default + AddAssign + SubAssign {
+    k: usize,
+    top: RemovableHeap<Reverse<T>>,
+    rest: RemovableHeap<T>,
+    sum: T, // of `top`
+}
+
+impl<T> TopK<T> where
+    T: Ord + Clone + Default + AddAssign + SubAssign {
+    pub fn new(k: usize) -> Self {
+        Self { k, top: RemovableHeap::new(), rest: RemovableHeap::new(), sum: T::default() }
+    }
+
+    pub fn insert(&mut self, value: T) {
+        if self.top.len() < self.k {
+            self.push_top(value);
+            return;
+        }
+        match self.top.peek() {
+            Some(Reverse(min)) if value > *min => {
+                let Reverse(min) = self.top.pop().unwrap();
+                self.sum -= min.clone();
+                self.rest.push(min);
+                self.push_top(value);
+            },
+            _ => self.rest.push(value),
+        }
+    }
+
+    /// `value` must currently be in the container.
+    pub fn erase(&mut self, value: T) {
+        let in_top = matches!(self.top.peek(), Some(Reverse(min)) if value >= *min);
+        if !in_top {
+            self.rest.remove(value);
+            return;
+        }
+        self.sum -= value.clone();
+        self.top.remove(Reverse(value));
+        if let Some(max) = self.rest.pop() {
+            self.push_top(max);
+        }
+    }
+
+    /// Sum of the `min(k, len)` largest elements.
+    pub fn sum(&self) -> T {
+        self.sum.clone()
+    }
+
+    /// The k-th largest element, if there are at least `k` elements.
+    pub fn kth_largest(&mut self) -> Option<T> {
+        if self.k == 0 || self.top.len() < self.k { return None; }
+        self.top.peek().map(|Reverse(min)| min.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.top.len() + self.rest.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push_top(&mut self, value: T) {
+        self.sum += value.clone();
+        self.top.push(Reverse(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_insert() {
+        let mut top = TopK::new(3);
+        top.insert(5);
+        top.insert(1);
+        assert_eq!(top.kth_largest(), None);
+        top.insert(4);
+        assert_eq!(top.kth_largest(), Some(1));
+        top.insert(9);
+        top.insert(2);
+        // {9, 5, 4} | {2, 1}
+        assert_eq!(top.kth_largest(), Some(4));
+        assert_eq!(top.sum(), 18);
+        assert_eq!(top.len(), 5);
+    }
+
+    #[test]
+    fn test_erase() {
+        let mut top = TopK::new(2);
+        for x in [3, 8, 8, 1, 6] {
+            top.insert(x);
+        }
+        // {8, 8} | {6, 3, 1}
+        assert_eq!(top.kth_largest(), Some(8));
+        top.erase(8);
+        assert_eq!(top.kth_largest(), Some(6));
+        top.erase(1);
+        assert_eq!(top.kth_largest(), Some(6));
+        top.erase(6);
+        assert_eq!(top.kth_largest(), Some(3));
+        top.erase(8);
+        top.erase(3);
+        assert_eq!(top.kth_largest(), None);
+        assert!(top.is_empty());
+    }
+
+    #[test]
+    fn test_random_against_sorted_vec() {
+        let mut rng = XorShift64::new(41);
+        for k in 0..6 {
+            let mut top = TopK::new(k);
+            let mut naive: Vec<i64> = Vec::new();
+            for _ in 0..300 {
+                if !naive.is_empty() && rng.gen_range(0..3) == 0 {
+                    let value = naive.remove(rng.gen_range(0..naive.len()));
+                    top.erase(value);
+                } else {
+                    let value = rng.gen_range(0..20) as i64 - 10;
+                    naive.push(value);
+                    top.insert(value);
+                }
+                naive.sort_unstable_by(|a, b| b.cmp(a));
+                let largest = &naive[..k.min(naive.len())];
+                assert_eq!(top.sum(), largest.iter().sum::<i64>());
+                assert_eq!(top.kth_largest(), if k > 0 && naive.len() >= k { Some(naive[k - 1]) } else { None });
+                assert_eq!(top.len(), naive.len());
+            }
+        }
+    }
+}
\ No newline at end of file
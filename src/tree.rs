@@ -0,0 +1,216 @@
+#[derive(Clone)]
+pub struct Lca {
+    depths: Vec<usize>,
+    ancestors: Vec<Vec<usize>>, // ancestors[k][v]: 2^k-th ancestor, saturating at the root
+}
+
+impl Lca {
+    pub fn new(adj: &[Vec<usize>], root: usize) -> Self {
+        let n = adj.len();
+        let mut depths = vec![0; n];
+        let mut parents = vec![root; n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![root];
+        visited[root] = true;
+        while let Some(u) = stack.pop() {
+            for &v in &adj[u] {
+                if !visited[v] {
+                    visited[v] = true;
+                    parents[v] = u;
+                    depths[v] = depths[u] + 1;
+                    stack.push(v);
+                }
+            }
+        }
+        let levels = (usize::BITS - n.leading_zeros()) as usize;
+        let mut ancestors = vec![parents];
+        for k in 1..levels {
+            let prev = &ancestors[k - 1];
+            let next = prev.iter().map(|&p| prev[p]).collect();
+            ancestors.push(next);
+        }
+        Self { depths, ancestors }
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depths[v]
+    }
+
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if k > self.depths[v] { return None; }
+        for (level, table) in self.ancestors.iter().enumerate() {
+            if (k >> level) & 1 == 1 {
+                v = table[v];
+            }
+        }
+        Some(v)
+    }
+
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = if self.depths[u] >= self.depths[v] { (u, v) } else { (v, u) };
+        u = self.kth_ancestor(u, self.depths[u] - self.depths[v]).unwrap();
+        if u == v { return u; }
+        for table in self.ancestors.iter().rev() {
+            if table[u] != table[v] {
+                u = table[u];
+                v = table[v];
+            }
+        }
+        self.ancestors[0][u]
+    }
+
+    pub fn distance(&self, u: usize, v: usize) -> usize {
+        let w = self.lca(u, v);
+        self.depths[u] + self.depths[v] - 2 * self.depths[w]
+    }
+
+    /// Level ancestor: the ancestor of `v` at `depth`.
+    pub fn la(&self, v: usize, depth: usize) -> Option<usize> {
+        self.kth_ancestor(v, self.depths[v].checked_sub(depth)?)
+    }
+
+    /// The `k`-th vertex on the path from `u` to `v`, with `u` itself at `k == 0`.
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let up = self.depths[u] - self.depths[w];
+        let down = self.depths[v] - self.depths[w];
+        if k <= up {
+            self.kth_ancestor(u, k)
+        } else if k <= up + down {
+            self.kth_ancestor(v, up + down - k)
+        } else {
+            None
+        }
+    }
+}
+
+/// Static vertex-weighted path queries on top of the LCA tables.
+#[derive(Clone)]
+pub struct PathAggregates {
+    lca: Lca,
+    weights: Vec<i64>,
+    prefix_sums: Vec<i64>, // sum of weights from the root to v, both inclusive
+    jump_maxima: Vec<Vec<i64>>, // jump_maxima[k][v]: max weight over 2^k vertices from v upwards
+}
+
+impl PathAggregates {
+    pub fn new(adj: &[Vec<usize>], root: usize, weights: &[i64]) -> Self {
+        let lca = Lca::new(adj, root);
+        let n = adj.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_unstable_by_key(|&v| lca.depths[v]);
+        let mut prefix_sums = vec![0; n];
+        for v in order {
+            let parent_sum = if v == root { 0 } else { prefix_sums[lca.ancestors[0][v]] };
+            prefix_sums[v] = parent_sum + weights[v];
+        }
+        let mut jump_maxima = vec![weights.to_vec()];
+        for k in 1..lca.ancestors.len() {
+            let prev = &jump_maxima[k - 1];
+            let next = (0..n).map(|v| prev[v].max(prev[lca.ancestors[k - 1][v]])).collect();
+            jump_maxima.push(next);
+        }
+        let weights = weights.to_vec();
+        Self { lca, weights, prefix_sums, jump_maxima }
+    }
+
+    pub fn lca(&self) -> &Lca {
+        &self.lca
+    }
+
+    pub fn path_sum(&self, u: usize, v: usize) -> i64 {
+        let w = self.lca.lca(u, v);
+        self.prefix_sums[u] + self.prefix_sums[v] - 2 * self.prefix_sums[w] + self.weights[w]
+    }
+
+    pub fn path_max(&self, u: usize, v: usize) -> i64 {
+        let w = self.lca.lca(u, v);
+        let from_u = self.max_upwards(u, self.lca.depths[u] - self.lca.depths[w] + 1);
+        let from_v = self.max_upwards(v, self.lca.depths[v] - self.lca.depths[w] + 1);
+        from_u.max(from_v)
+    }
+
+    fn max_upwards(&self, mut v: usize, count: usize) -> i64 {
+        let mut result = i64::MIN;
+        for (level, table) in self.lca.ancestors.iter().enumerate() {
+            if (count >> level) & 1 == 1 {
+                result = result.max(self.jump_maxima[level][v]);
+                v = table[v];
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //        0
+    //      /   \
+    //     1     2
+    //    / \     \
+    //   3   4     5
+    //       |
+    //       6
+    fn sample_tree() -> Vec<Vec<usize>> {
+        let edges = [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (4, 6)];
+        let mut adj = vec![Vec::new(); 7];
+        for &(u, v) in &edges {
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+        adj
+    }
+
+    #[test]
+    fn test_lca() {
+        let lca = Lca::new(&sample_tree(), 0);
+        assert_eq!(lca.lca(3, 6), 1);
+        assert_eq!(lca.lca(6, 5), 0);
+        assert_eq!(lca.lca(4, 6), 4);
+        assert_eq!(lca.lca(2, 2), 2);
+        assert_eq!(lca.distance(3, 5), 4);
+    }
+
+    #[test]
+    fn test_kth_ancestor() {
+        let lca = Lca::new(&sample_tree(), 0);
+        assert_eq!(lca.kth_ancestor(6, 0), Some(6));
+        assert_eq!(lca.kth_ancestor(6, 1), Some(4));
+        assert_eq!(lca.kth_ancestor(6, 2), Some(1));
+        assert_eq!(lca.kth_ancestor(6, 3), Some(0));
+        assert_eq!(lca.kth_ancestor(6, 4), None);
+        assert_eq!(lca.kth_ancestor(0, 1), None);
+    }
+
+    #[test]
+    fn test_level_ancestor_and_jump() {
+        let lca = Lca::new(&sample_tree(), 0);
+        assert_eq!(lca.la(6, 1), Some(1));
+        assert_eq!(lca.la(6, 3), Some(6));
+        assert_eq!(lca.la(6, 4), None);
+
+        // 3 -> 1 -> 0 -> 2 -> 5
+        let path: Vec<_> = (0..6).map(|k| lca.jump(3, 5, k)).collect();
+        assert_eq!(path, [Some(3), Some(1), Some(0), Some(2), Some(5), None]);
+        assert_eq!(lca.jump(6, 1, 2), Some(1));
+        assert_eq!(lca.jump(1, 6, 2), Some(6));
+        assert_eq!(lca.jump(2, 2, 0), Some(2));
+    }
+
+    #[test]
+    fn test_path_aggregates() {
+        let weights = [5, -2, 7, 4, 1, 3, 9];
+        let paths = PathAggregates::new(&sample_tree(), 0, &weights);
+
+        // 3 -> 1 -> 4 -> 6
+        assert_eq!(paths.path_sum(3, 6), 4 - 2 + 1 + 9);
+        assert_eq!(paths.path_max(3, 6), 9);
+        // 4 -> 1 -> 0 -> 2 -> 5
+        assert_eq!(paths.path_sum(4, 5), 1 - 2 + 5 + 7 + 3);
+        assert_eq!(paths.path_max(4, 5), 7);
+        assert_eq!(paths.path_sum(1, 1), -2);
+        assert_eq!(paths.path_max(1, 1), -2);
+    }
+}
\ No newline at end of file
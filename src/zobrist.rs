@@ -0,0 +1,173 @@
+use std::hash::Hash;
+use std::ops::{Range, RangeBounds};
+
+use crate::fast_hash::{FastHashMap, FastHashSet};
+use crate::fenwick::{FenwickCompatible, PointFenwickTree};
+use crate::range::to_half_open;
+use crate::rng::XorShift64;
+
+/// Assigns each distinct value a random `u64`, so sets and multisets hash by combining codes:
+/// xor for sets (and parities), wrapping addition for multisets.
+#[derive(Clone)]
+pub struct Zobrist<T> {
+    rng: XorShift64,
+    codes: FastHashMap<T, u64>,
+}
+
+impl<T> Zobrist<T> where
+    T: Hash + Eq + Clone {
+    /// Seeded by `rng::random_seed`.
+    pub fn new() -> Self {
+        Self::with_rng(XorShift64::from_random_seed())
+    }
+
+    pub fn with_rng(rng: XorShift64) -> Self {
+        Self { rng, codes: FastHashMap::default() }
+    }
+
+    pub fn code(&mut self, x: &T) -> u64 {
+        if let Some(&code) = self.codes.get(x) { return code; }
+        let code = self.rng.next_u64();
+        self.codes.insert(x.clone(), code);
+        code
+    }
+
+    /// Equal exactly (up to collisions) when the values form the same multiset.
+    pub fn multiset_hash<'a>(&mut self, values: impl IntoIterator<Item = &'a T>) -> u64 where
+        T: 'a {
+        values.into_iter().fold(0, |acc, x| acc.wrapping_add(self.code(x)))
+    }
+
+    /// Ignores multiplicities.
+    pub fn set_hash<'a>(&mut self, values: impl IntoIterator<Item = &'a T>) -> u64 where
+        T: 'a {
+        let distinct: FastHashSet<&T> = values.into_iter().collect();
+        distinct.into_iter().fold(0, |acc, x| acc ^ self.code(x))
+    }
+
+    /// `result[i]` is the multiset hash of `a[..i]`; compare ranges with `range_hash`.
+    pub fn prefix_multiset(&mut self, a: &[T]) -> Vec<u64> {
+        let mut result = vec![0u64];
+        for x in a {
+            result.push(result.last().unwrap().wrapping_add(self.code(x)));
+        }
+        result
+    }
+
+    /// `result[i]` is the set hash of `a[..i]`. Only prefixes compare this way;
+    /// use `set_hashes_in_ranges` for arbitrary ranges.
+    pub fn prefix_set(&mut self, a: &[T]) -> Vec<u64> {
+        let mut seen = FastHashSet::default();
+        let mut result = vec![0u64];
+        for x in a {
+            let mut hash = *result.last().unwrap();
+            if seen.insert(x) {
+                hash ^= self.code(x);
+            }
+            result.push(hash);
+        }
+        result
+    }
+
+    /// Set hash of each `a[range]`, answered offline like `count_distinct_in_ranges`:
+    /// sweeping right ends, only the last occurrence of each value keeps its code.
+    /// O((n + q) log n).
+    pub fn set_hashes_in_ranges(&mut self, a: &[T], ranges: &[Range<usize>]) -> Vec<u64> {
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.sort_unstable_by_key(|&q| ranges[q].end);
+        let mut marks = PointFenwickTree::<Xor>::new(a.len());
+        let mut last_seen = FastHashMap::default();
+        let mut result = vec![0; ranges.len()];
+        let mut swept = 0;
+        for q in order {
+            while swept < ranges[q].end {
+                let code = Xor(self.code(&a[swept]));
+                if let Some(prev) = last_seen.insert(&a[swept], swept) {
+                    marks.add(prev, code);
+                }
+                marks.add(swept, code);
+                swept += 1;
+            }
+            result[q] = marks.sum(ranges[q].clone()).0;
+        }
+        result
+    }
+}
+
+/// Codes combined by xor, which is its own inverse.
+#[derive(Clone, Copy)]
+struct Xor(u64);
+
+impl FenwickCompatible for Xor {
+    fn zero() -> Self { Xor(0) }
+    fn neg(self) -> Self { self }
+    fn add(self, rhs: Self) -> Self { Xor(self.0 ^ rhs.0) }
+    fn sub(self, rhs: Self) -> Self { Xor(self.0 ^ rhs.0) }
+    fn scale(self, n: usize) -> Self { if n % 2 == 1 { self } else { Xor(0) } }
+}
+
+impl<T> Default for Zobrist<T> where
+    T: Hash + Eq + Clone {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Multiset hash of a range, from a table built by `Zobrist::prefix_multiset`.
+pub fn range_hash(prefix: &[u64], range: impl RangeBounds<usize>) -> u64 {
+    let (begin, end) = to_half_open(range, prefix.len() - 1);
+    prefix[end].wrapping_sub(prefix[begin])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_multisets() {
+        let mut zobrist = Zobrist::with_rng(XorShift64::new(20));
+        let a = [3, 1, 4, 1, 5, 9, 2, 6];
+        let b = [1, 1, 4, 3, 2, 9, 5, 6];
+        let (pa, pb) = (zobrist.prefix_multiset(&a), zobrist.prefix_multiset(&b));
+        assert_eq!(range_hash(&pa, ..4), range_hash(&pb, ..4));
+        assert_ne!(range_hash(&pa, ..3), range_hash(&pb, ..3));
+        assert_eq!(range_hash(&pa, 4..7), range_hash(&pb, 4..7));
+        assert_eq!(range_hash(&pa, 1..4), zobrist.multiset_hash(&[1, 4, 1]));
+    }
+
+    #[test]
+    fn test_prefix_sets() {
+        let mut zobrist = Zobrist::with_rng(XorShift64::new(21));
+        let a = [1, 2, 1, 3, 3];
+        let b = [2, 2, 1, 1, 3];
+        let (sa, sb) = (zobrist.prefix_set(&a), zobrist.prefix_set(&b));
+        assert_eq!(sa[2], sb[3]);
+        assert_eq!(sa[5], sb[5]);
+        assert_ne!(sa[1], sb[1]);
+        assert_ne!(sa[4], sb[3]);
+        assert_eq!(sa[5], zobrist.set_hash(&[3, 2, 1, 1]));
+        assert_eq!(zobrist.set_hash(&[]), 0);
+    }
+
+    #[test]
+    fn test_set_hashes_in_ranges() {
+        let mut zobrist = Zobrist::with_rng(XorShift64::new(22));
+        let mut rng = XorShift64::new(23);
+        for n in 1..=30 {
+            let a: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n / 3 + 1)).collect();
+            let ranges: Vec<_> = (0..50)
+                .map(|_| {
+                    let begin = rng.gen_range(0..n + 1);
+                    begin..rng.gen_range(begin..n + 1)
+                })
+                .collect();
+            let expected: Vec<u64> = ranges.iter().map(|r| zobrist.set_hash(&a[r.clone()])).collect();
+            assert_eq!(zobrist.set_hashes_in_ranges(&a, &ranges), expected);
+        }
+        let a = [1, 2, 1, 3, 2, 3];
+        let hashes = zobrist.set_hashes_in_ranges(&a, &[0..3, 3..5, 2..5, 1..3]);
+        assert_eq!(hashes[2], hashes[3] ^ zobrist.code(&3));
+        assert_ne!(hashes[0], hashes[1]);
+        assert_eq!(hashes[0], hashes[3]);
+    }
+}
\ No newline at end of file